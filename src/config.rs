@@ -1,10 +1,33 @@
+use crate::{
+    error::BinanceError::{self, *},
+    models::Product,
+};
+use fehler::{throw, throws};
+use url::Url;
+
+/// The Binance environment a `Config` points to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Environment {
+    /// The live exchange.
+    #[default]
+    Production,
+    /// The public testnets (testnet.binance.vision / binancefuture.com).
+    Testnet,
+    /// The demo trading environment, which mirrors production market data with paper balances.
+    Demo,
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
+    pub environment: Environment,
+
     pub rest_api_endpoint: String,
     pub ws_endpoint: String,
+    pub ws_api_endpoint: String,
 
     pub usdm_futures_rest_api_endpoint: String,
     pub usdm_futures_ws_endpoint: String,
+    pub usdm_futures_ws_api_endpoint: String,
 
     pub coinm_futures_rest_api_endpoint: String,
     pub coinm_futures_ws_endpoint: String,
@@ -20,18 +43,28 @@ pub struct Config {
 
 impl Default for Config {
     fn default() -> Self {
+        Self::production()
+    }
+}
+
+impl Config {
+    pub fn production() -> Self {
         Self {
+            environment: Environment::Production,
+
             rest_api_endpoint: "https://api.binance.com".into(),
             ws_endpoint: "wss://stream.binance.com:9443".into(),
+            ws_api_endpoint: "wss://ws-api.binance.com:443/ws-api/v3".into(),
 
             usdm_futures_rest_api_endpoint: "https://fapi.binance.com".into(),
             usdm_futures_ws_endpoint: "wss://fstream.binance.com".into(),
+            usdm_futures_ws_api_endpoint: "wss://ws-fapi.binance.com/ws-fapi/v1".into(),
 
             coinm_futures_rest_api_endpoint: "https://dapi.binance.com".into(),
             coinm_futures_ws_endpoint: "wss://dstream.binance.com".into(),
 
             european_options_rest_api_endpoint: "https://eapi.binance.com".into(),
-            european_options_ws_endpoint: "wss://estream.binance.com".into(),
+            european_options_ws_endpoint: "wss://nbstream.binance.com/eoptions".into(),
 
             portfolio_margin_rest_api_endpoint: "https://papi.binance.com".into(),
             portfolio_margin_ws_endpoint: "wss://fstream.binance.com/pm".into(),
@@ -39,15 +72,234 @@ impl Default for Config {
             recv_window: 5000,
         }
     }
-}
 
-impl Config {
+    /// Binance does not run a dedicated portfolio margin testnet, so the portfolio margin
+    /// endpoints fall back to the futures testnet hosts.
     pub fn testnet() -> Self {
-        // Self::default()
-        //     .set_rest_api_endpoint("https://testnet.binance.vision")
-        //     .set_ws_endpoint("wss://testnet.binance.vision/ws")
-        //     .set_futures_rest_api_endpoint("https://testnet.binancefuture.com")
-        //     .set_futures_ws_endpoint("https://testnet.binancefuture.com/ws")
-        todo!()
+        Self {
+            environment: Environment::Testnet,
+
+            rest_api_endpoint: "https://testnet.binance.vision".into(),
+            ws_endpoint: "wss://stream.testnet.binance.vision".into(),
+            ws_api_endpoint: "wss://ws-api.testnet.binance.vision/ws-api/v3".into(),
+
+            usdm_futures_rest_api_endpoint: "https://testnet.binancefuture.com".into(),
+            usdm_futures_ws_endpoint: "wss://fstream.binancefuture.com".into(),
+            usdm_futures_ws_api_endpoint: "wss://testnet.binancefuture.com/ws-fapi/v1".into(),
+
+            coinm_futures_rest_api_endpoint: "https://testnet.binancefuture.com".into(),
+            coinm_futures_ws_endpoint: "wss://dstream.binancefuture.com".into(),
+
+            european_options_rest_api_endpoint: "https://testnet.binanceops.com".into(),
+            european_options_ws_endpoint: "wss://testnetws.binanceops.com".into(),
+
+            portfolio_margin_rest_api_endpoint: "https://testnet.binancefuture.com".into(),
+            portfolio_margin_ws_endpoint: "wss://fstream.binancefuture.com/pm".into(),
+
+            recv_window: 5000,
+        }
+    }
+
+    /// Options and portfolio margin are not part of demo trading and fall back to the testnet hosts.
+    pub fn demo() -> Self {
+        Self {
+            environment: Environment::Demo,
+
+            rest_api_endpoint: "https://demo-api.binance.com".into(),
+            ws_endpoint: "wss://demo-stream.binance.com:9443".into(),
+            ws_api_endpoint: "wss://demo-ws-api.binance.com/ws-api/v3".into(),
+
+            usdm_futures_rest_api_endpoint: "https://demo-fapi.binance.com".into(),
+            usdm_futures_ws_endpoint: "wss://fstream.binancefuture.com".into(),
+
+            coinm_futures_rest_api_endpoint: "https://demo-dapi.binance.com".into(),
+            coinm_futures_ws_endpoint: "wss://dstream.binancefuture.com".into(),
+
+            ..Self::testnet()
+        }
+    }
+
+    pub fn for_environment(environment: Environment) -> Self {
+        match environment {
+            Environment::Production => Self::production(),
+            Environment::Testnet => Self::testnet(),
+            Environment::Demo => Self::demo(),
+        }
+    }
+
+    /// Start from the endpoints of `environment` and override them piece by piece.
+    pub fn builder(environment: Environment) -> ConfigBuilder {
+        ConfigBuilder {
+            config: Self::for_environment(environment),
+        }
+    }
+
+    pub fn rest_endpoint(&self, product: Product) -> &str {
+        match product {
+            Product::Spot | Product::Sapi => &self.rest_api_endpoint,
+            Product::UsdMFutures => &self.usdm_futures_rest_api_endpoint,
+            Product::CoinMFutures => &self.coinm_futures_rest_api_endpoint,
+            Product::EuropeanOptions => &self.european_options_rest_api_endpoint,
+            Product::PortfolioMargin => &self.portfolio_margin_rest_api_endpoint,
+        }
+    }
+
+    pub fn ws_endpoint(&self, product: Product) -> &str {
+        match product {
+            Product::Spot | Product::Sapi => &self.ws_endpoint,
+            Product::UsdMFutures => &self.usdm_futures_ws_endpoint,
+            Product::CoinMFutures => &self.coinm_futures_ws_endpoint,
+            Product::EuropeanOptions => &self.european_options_ws_endpoint,
+            Product::PortfolioMargin => &self.portfolio_margin_ws_endpoint,
+        }
+    }
+
+    /// The websocket API host of `product`. Only spot and USD-M futures have one.
+    pub fn ws_api_endpoint(&self, product: Product) -> Option<&str> {
+        match product {
+            Product::Spot => Some(&self.ws_api_endpoint),
+            Product::UsdMFutures => Some(&self.usdm_futures_ws_api_endpoint),
+            _ => None,
+        }
+    }
+
+    fn rest_endpoint_mut(&mut self, product: Product) -> &mut String {
+        match product {
+            Product::Spot | Product::Sapi => &mut self.rest_api_endpoint,
+            Product::UsdMFutures => &mut self.usdm_futures_rest_api_endpoint,
+            Product::CoinMFutures => &mut self.coinm_futures_rest_api_endpoint,
+            Product::EuropeanOptions => &mut self.european_options_rest_api_endpoint,
+            Product::PortfolioMargin => &mut self.portfolio_margin_rest_api_endpoint,
+        }
+    }
+
+    fn ws_endpoint_mut(&mut self, product: Product) -> &mut String {
+        match product {
+            Product::Spot | Product::Sapi => &mut self.ws_endpoint,
+            Product::UsdMFutures => &mut self.usdm_futures_ws_endpoint,
+            Product::CoinMFutures => &mut self.coinm_futures_ws_endpoint,
+            Product::EuropeanOptions => &mut self.european_options_ws_endpoint,
+            Product::PortfolioMargin => &mut self.portfolio_margin_ws_endpoint,
+        }
+    }
+
+    /// Check that every endpoint is an absolute URL with the right scheme and that
+    /// `recv_window` is within the 60000ms Binance accepts.
+    #[throws(BinanceError)]
+    pub fn validate(&self) {
+        for product in Product::ALL {
+            check_endpoint(self.rest_endpoint(product), &["https", "http"])?;
+            check_endpoint(self.ws_endpoint(product), &["wss", "ws"])?;
+            if let Some(endpoint) = self.ws_api_endpoint(product) {
+                check_endpoint(endpoint, &["wss", "ws"])?;
+            }
+        }
+
+        if self.recv_window == 0 || self.recv_window > 60000 {
+            throw!(InvalidConfig(format!(
+                "recv_window must be within 1..=60000, got {}",
+                self.recv_window
+            )))
+        }
+    }
+}
+
+#[throws(BinanceError)]
+fn check_endpoint(endpoint: &str, schemes: &[&str]) {
+    let url = match Url::parse(endpoint) {
+        Ok(url) => url,
+        Err(e) => throw!(InvalidConfig(format!("{endpoint}: {e}"))),
+    };
+    if !schemes.contains(&url.scheme()) {
+        throw!(InvalidConfig(format!(
+            "{endpoint}: scheme must be one of {schemes:?}"
+        )))
+    }
+    if url.query().is_some() || endpoint.ends_with('/') {
+        throw!(InvalidConfig(format!(
+            "{endpoint}: endpoint must not end with '/' or carry a query"
+        )))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn rest_endpoint(mut self, product: Product, endpoint: impl Into<String>) -> Self {
+        *self.config.rest_endpoint_mut(product) = endpoint.into();
+        self
+    }
+
+    pub fn ws_endpoint(mut self, product: Product, endpoint: impl Into<String>) -> Self {
+        *self.config.ws_endpoint_mut(product) = endpoint.into();
+        self
+    }
+
+    /// Ignored for products without a websocket API.
+    pub fn ws_api_endpoint(mut self, product: Product, endpoint: impl Into<String>) -> Self {
+        match product {
+            Product::Spot => self.config.ws_api_endpoint = endpoint.into(),
+            Product::UsdMFutures => self.config.usdm_futures_ws_api_endpoint = endpoint.into(),
+            _ => {}
+        }
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.config.recv_window = recv_window;
+        self
+    }
+
+    #[throws(BinanceError)]
+    pub fn build(self) -> Config {
+        self.config.validate()?;
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Config, Environment};
+    use crate::models::Product;
+
+    #[test]
+    fn presets_are_valid() {
+        for env in [
+            Environment::Production,
+            Environment::Testnet,
+            Environment::Demo,
+        ] {
+            let config = Config::for_environment(env);
+            assert_eq!(config.environment, env);
+            config.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn builder_overrides_and_validates() {
+        let config = Config::builder(Environment::Testnet)
+            .rest_endpoint(Product::UsdMFutures, "http://localhost:8080")
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.rest_endpoint(Product::UsdMFutures),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            config.rest_endpoint(Product::Spot),
+            "https://testnet.binance.vision"
+        );
+
+        assert!(Config::builder(Environment::Production)
+            .ws_endpoint(Product::Spot, "https://stream.binance.com")
+            .build()
+            .is_err());
+        assert!(Config::builder(Environment::Production)
+            .recv_window(60001)
+            .build()
+            .is_err());
     }
 }
@@ -1,5 +1,7 @@
+use crate::models::Product;
 use http::{header::InvalidHeaderValue, StatusCode};
 use serde::Deserialize;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -14,8 +16,12 @@ pub enum BinanceError {
     MissingApiKey,
     #[error("No Api secret set for private api")]
     MissingApiSecret,
+    #[error("Cannot load credentials: {0}")]
+    LoadCredentials(String),
     #[error("Websocket is closed")]
     WebsocketClosed,
+    #[error("No frame received from websocket in {0:?}")]
+    WebsocketTimeout(Duration),
     #[error("Topics is empty")]
     EmptyTopics,
     #[error("Unknown stream {0}")]
@@ -28,11 +34,44 @@ pub enum BinanceError {
     StartWebsocketError(StatusCode, String),
     #[error("The field for the given event type {0} in user data stream is empty")]
     EmptyUserDataStream(String),
+    #[error("Invalid depth level: {0}")]
+    InvalidDepth(String),
+    #[error("Invalid option symbol: {0}")]
+    InvalidOptionSymbol(String),
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("{0:?} has no websocket API")]
+    WsApiUnsupported(Product),
+    #[error("Request for {0:?} sent to the {1:?} websocket API")]
+    WsApiProductMismatch(Product, Product),
+    #[error("No response to {0} in {1:?}")]
+    WsApiTimeout(String, Duration),
+    #[error("Rate limit of {0:?} reached, the window resets in {1:?}")]
+    RateLimited(Product, Duration),
     #[error("Binance returns error: {code} - {msg}")]
     BinanceResponse { code: i64, msg: String },
+    /// A non-2xx response whose body is a Binance error object.
+    #[error("{endpoint} returns {status}: {} - {msg}", code.code())]
+    ApiError {
+        status: StatusCode,
+        endpoint: String,
+        code: BinanceErrorCode,
+        msg: String,
+        retry_after: Option<Duration>,
+    },
+    /// A non-2xx response without a Binance error object, e.g. a 5xx from the gateway or a 403 from the WAF.
+    #[error("{endpoint} returns {status}: {body}")]
+    HttpError {
+        status: StatusCode,
+        endpoint: String,
+        body: String,
+        retry_after: Option<Duration>,
+    },
 
     #[error(transparent)]
-    Websocket(#[from] tungstenite::Error),
+    Websocket(Box<tungstenite::Error>),
     #[error(transparent)]
     SerdeQs(#[from] serde_qs::Error),
     #[error(transparent)]
@@ -43,6 +82,56 @@ pub enum BinanceError {
     SerdeJson(#[from] serde_json::Error),
 }
 
+impl BinanceError {
+    /// The Binance error code, if Binance returned one.
+    pub fn code(&self) -> Option<BinanceErrorCode> {
+        match self {
+            Self::BinanceResponse { code, .. } => Some(BinanceErrorCode::from(*code)),
+            Self::ApiError { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status of a failed REST request.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::ApiError { status, .. } | Self::HttpError { status, .. } => Some(*status),
+            Self::Reqwest(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// How long Binance asked us to back off, from the `Retry-After` header.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::ApiError { retry_after, .. } | Self::HttpError { retry_after, .. } => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+
+    /// HTTP 429: the request rate limit was broken.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// HTTP 418: the IP was auto-banned for continuing to send requests after a 429.
+    pub fn is_ip_banned(&self) -> bool {
+        self.status() == Some(StatusCode::IM_A_TEAPOT)
+    }
+
+    /// HTTP 403: the request was blocked by the web application firewall.
+    pub fn is_waf_violation(&self) -> bool {
+        self.status() == Some(StatusCode::FORBIDDEN)
+    }
+
+    /// HTTP 5xx: an issue on Binance's side. The request may still have been executed.
+    pub fn is_server_error(&self) -> bool {
+        matches!(self.status(), Some(s) if s.is_server_error())
+    }
+}
+
 impl From<BinanceResponseError> for BinanceError {
     fn from(v: BinanceResponseError) -> Self {
         Self::BinanceResponse {
@@ -51,3 +140,150 @@ impl From<BinanceResponseError> for BinanceError {
         }
     }
 }
+
+impl From<tungstenite::Error> for BinanceError {
+    fn from(e: tungstenite::Error) -> Self {
+        Self::Websocket(Box::new(e))
+    }
+}
+
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $name:ident = $code:literal,)*) => {
+        /// Error codes documented by Binance. Codes above -2016 follow the futures
+        /// definitions; spot reuses a few of them for cancel-replace failures.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum BinanceErrorCode {
+            $($(#[$doc])* $name,)*
+            /// A code this library does not know about.
+            Other(i64),
+        }
+
+        impl BinanceErrorCode {
+            pub fn code(&self) -> i64 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Other(code) => *code,
+                }
+            }
+        }
+
+        impl From<i64> for BinanceErrorCode {
+            fn from(code: i64) -> Self {
+                match code {
+                    $($code => Self::$name,)*
+                    code => Self::Other(code),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// An unknown error occurred while processing the request.
+    Unknown = -1000,
+    /// Internal error; unable to process your request. Please try again.
+    Disconnected = -1001,
+    /// You are not authorized to execute this request.
+    Unauthorized = -1002,
+    /// Too many requests queued or the request weight limit was broken.
+    TooManyRequests = -1003,
+    /// Server is busy, please wait and try again.
+    ServerBusy = -1004,
+    /// An unexpected response was received from the message bus. Execution status unknown.
+    UnexpectedResponse = -1006,
+    /// Timeout waiting for response from backend server. Execution status unknown.
+    Timeout = -1007,
+    /// The server is overloaded with other requests.
+    ServerOverloaded = -1008,
+    /// Unsupported order combination.
+    UnknownOrderComposition = -1014,
+    /// Too many new orders.
+    TooManyOrders = -1015,
+    /// This service is no longer available.
+    ServiceShuttingDown = -1016,
+    /// This operation is not supported.
+    UnsupportedOperation = -1020,
+    /// Timestamp for this request is outside of the recvWindow.
+    InvalidTimestamp = -1021,
+    /// Signature for this request is not valid.
+    InvalidSignature = -1022,
+    /// Illegal characters found in a parameter.
+    IllegalChars = -1100,
+    /// Too many parameters sent for this endpoint.
+    TooManyParameters = -1101,
+    /// A mandatory parameter was not sent, was empty/null, or malformed.
+    MandatoryParamEmptyOrMalformed = -1102,
+    /// An unknown parameter was sent.
+    UnknownParam = -1103,
+    /// Not all sent parameters were read.
+    UnreadParameters = -1104,
+    /// A parameter was empty.
+    ParamEmpty = -1105,
+    /// A parameter was sent when not required.
+    ParamNotRequired = -1106,
+    /// Precision is over the maximum defined for this asset.
+    BadPrecision = -1111,
+    /// No orders on book for symbol.
+    NoDepth = -1112,
+    /// TimeInForce parameter sent when not required.
+    TifNotRequired = -1114,
+    /// Invalid timeInForce.
+    InvalidTif = -1115,
+    /// Invalid orderType.
+    InvalidOrderType = -1116,
+    /// Invalid side.
+    InvalidSide = -1117,
+    /// New client order ID was empty.
+    EmptyNewClientOrderId = -1118,
+    /// Original client order ID was empty.
+    EmptyOrigClientOrderId = -1119,
+    /// Invalid interval.
+    BadInterval = -1120,
+    /// Invalid symbol.
+    BadSymbol = -1121,
+    /// This listenKey does not exist.
+    InvalidListenKey = -1125,
+    /// Lookup interval is too big.
+    MoreThanXxHours = -1127,
+    /// Combination of optional parameters invalid.
+    OptionalParamsBadCombo = -1128,
+    /// Invalid data sent for a parameter.
+    InvalidParameter = -1130,
+    /// recvWindow must be less than 60000.
+    BadRecvWindow = -1131,
+    /// NEW_ORDER_REJECTED
+    NewOrderRejected = -2010,
+    /// CANCEL_REJECTED
+    CancelRejected = -2011,
+    /// Order does not exist.
+    NoSuchOrder = -2013,
+    /// API-key format invalid.
+    BadApiKeyFormat = -2014,
+    /// Invalid API-key, IP, or permissions for action.
+    RejectedMbxKey = -2015,
+    /// No trading window could be found for the symbol.
+    NoTradingWindow = -2016,
+    /// Balance is insufficient.
+    BalanceNotSufficient = -2018,
+    /// Margin is insufficient.
+    MarginNotSufficient = -2019,
+    /// Unable to fill.
+    UnableToFill = -2020,
+    /// Order would immediately trigger.
+    OrderWouldImmediatelyTrigger = -2021,
+    /// ReduceOnly Order is rejected.
+    ReduceOnlyReject = -2022,
+    /// User in liquidation mode now.
+    UserInLiquidation = -2023,
+    /// Position is not sufficient.
+    PositionNotSufficient = -2024,
+    /// Reach max open order limit.
+    MaxOpenOrderExceeded = -2025,
+    /// This OrderType is not supported when reduceOnly.
+    ReduceOnlyOrderTypeNotSupported = -2026,
+    /// Exceeded the maximum allowable position at current leverage.
+    MaxLeverageRatio = -2027,
+    /// Leverage is smaller than permitted: insufficient margin balance.
+    MinLeverageRatio = -2028,
+}
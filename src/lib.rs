@@ -102,9 +102,13 @@ mod parser;
 pub mod rest;
 pub mod websocket;
 
-pub use config::Config;
-pub use error::{BinanceError, BinanceResponseError};
+pub use config::{Config, ConfigBuilder, Environment};
+pub use error::{BinanceError, BinanceErrorCode, BinanceResponseError};
 #[cfg(feature = "zero-copy")]
 pub use rest::C;
-pub use rest::{Binance, RestResponse};
-pub use websocket::BinanceWebsocket;
+pub use rest::{
+    ApiKey, Binance, Credentials, Ed25519Signer, EnvCredentials, ExponentialBackoff,
+    FileCredentials, HmacSigner, RateLimitScope, RateLimitUsage, RestResponse, RetryPolicy,
+    RsaSigner, Secret, Signer,
+};
+pub use websocket::{BinanceWebsocket, BinanceWsApi, ReconnectingWebsocket, WebsocketEvent};
@@ -1,3 +1,12 @@
+/// Define a request struct, its response and the `Request` implementation connecting them.
+///
+/// `Keyed`, `Weight`, `IsOrder` and `UidWeight` are optional. `Weight` is either a constant
+/// (`Weight => 5;`) or computed from the request
+/// (`Weight(req) => if req.symbol.is_some() { 1 } else { 40 };`), and defaults to 1.
+/// `IsOrder => true;` marks calls that count against the ORDERS limits, `UidWeight => true;`
+/// the `/sapi` calls whose weight counts per account instead of per IP.
+///
+/// The build fails when `Endpoint` is not on the REST host of `Product`.
 #[macro_export]
 macro_rules! define_request {
     (
@@ -5,82 +14,49 @@ macro_rules! define_request {
         Product => $product: expr;
         Method => $method: expr;
         Endpoint => $endpoint: expr;
+        $(Keyed => $keyed: expr;)?
         Signed => $signed: expr;
-        Request => { $($req_def:tt)* };
-        Response => { $($resp_def:tt)* };
-    ) => {
-        crate::define_request! {
-            Name => $name;
-            Product => $product;
-            Method => $method;
-            Endpoint => $endpoint;
-            Keyed => false;
-            Signed => $signed;
-            Request => { $($req_def)* };
-            Response => { $($resp_def)* };
-        }
-    };
-    (
-        Name => $name: ident;
-        Product => $product: expr;
-        Method => $method: expr;
-        Endpoint => $endpoint: expr;
-        Keyed => $keyed: expr;
-        Signed => $signed: expr;
+        $(Weight => $weight: expr;)?
+        $(Weight($req: ident) => $weight_fn: expr;)?
+        $(IsOrder => $is_order: expr;)?
+        $(UidWeight => $uid_weight: expr;)?
         Request => { $($req_def:tt)* };
         Response => { $($resp_def:tt)* };
     ) => {
         paste::paste! {
-            #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
-            pub struct [<$name Request>] {
-                $($req_def)*
-            }
-
             #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
             #[serde(rename_all = "camelCase")]
             pub struct [<$name Response>] {
                 $($resp_def)*
             }
 
-            impl crate::rest::Request for [<$name Request>] {
-                const PRODUCT: crate::rest::Product = $product;
-                const ENDPOINT: &'static str = $endpoint;
-                const METHOD: reqwest::Method = $method;
-                const KEYED: bool = $keyed;
-                const SIGNED: bool = $signed;
-                type Response = [<$name Response>];
+            $crate::define_request! {
+                Name => $name;
+                Product => $product;
+                Method => $method;
+                Endpoint => $endpoint;
+                $(Keyed => $keyed;)?
+                Signed => $signed;
+                $(Weight => $weight;)?
+                $(Weight($req) => $weight_fn;)?
+                $(IsOrder => $is_order;)?
+                $(UidWeight => $uid_weight;)?
+                Request => { $($req_def)* };
+                Response => [<$name Response>];
             }
         }
     };
-
     (
         Name => $name: ident;
         Product => $product: expr;
         Method => $method: expr;
         Endpoint => $endpoint: expr;
+        $(Keyed => $keyed: expr;)?
         Signed => $signed: expr;
-        Request => { $($req_def:tt)* };
-        Response => $resp_ty: ty;
-    ) => {
-        crate::define_request! {
-            Name => $name;
-            Product => $product;
-            Method => $method;
-            Endpoint => $endpoint;
-            Keyed => false;
-            Signed => $signed;
-            Request => { $($req_def)* };
-            Response => $resp_ty;
-        }
-    };
-    (
-        Name => $name: ident;
-        Product => $product: expr;
-        Method => $method: expr;
-        Endpoint => $endpoint: expr;
-        Keyed => $keyed: expr;
-        Signed => $signed: expr;
+        $(Weight => $weight: expr;)?
+        $(Weight($req: ident) => $weight_fn: expr;)?
+        $(IsOrder => $is_order: expr;)?
+        $(UidWeight => $uid_weight: expr;)?
         Request => { $($req_def:tt)* };
         Response => $resp_ty: ty;
     ) => {
@@ -91,14 +67,33 @@ macro_rules! define_request {
                 $($req_def)*
             }
 
-            impl crate::rest::Request for [<$name Request>] {
-                const PRODUCT: crate::rest::Product = $product;
+            impl $crate::rest::Request for [<$name Request>] {
+                const PRODUCT: $crate::models::Product = $product;
                 const ENDPOINT: &'static str = $endpoint;
                 const METHOD: reqwest::Method = $method;
-                const KEYED: bool = $keyed;
+                const KEYED: bool = false $(|| $keyed)?;
                 const SIGNED: bool = $signed;
+                const IS_ORDER: bool = false $(|| $is_order)?;
+                const UID_WEIGHT: bool = false $(|| $uid_weight)?;
                 type Response = $resp_ty;
+
+                $(
+                    fn weight(&self) -> u64 {
+                        $weight
+                    }
+                )?
+                $(
+                    fn weight(&self) -> u64 {
+                        let $req = self;
+                        $weight_fn
+                    }
+                )?
             }
+
+            const _: () = assert!(
+                $crate::models::Product::serves($product, $endpoint),
+                concat!(stringify!($name), ": ", $endpoint, " is not an endpoint of ", stringify!($product)),
+            );
         }
     };
 }
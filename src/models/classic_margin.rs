@@ -0,0 +1,75 @@
+use crate::{models::spot::OrderInfo, parser::string_or_decimal};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// How a margin order borrows the missing assets and repays the debt it settles.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SideEffectType {
+    #[default]
+    NoSideEffect,
+    MarginBuy,
+    AutoRepay,
+    AutoBorrowRepay,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BorrowRepayType {
+    #[default]
+    Borrow,
+    Repay,
+}
+
+/// A cross or isolated margin order as returned by the order queries.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub is_isolated: bool,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cummulative_quote_qty: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub side: String,
+    #[serde(with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub iceberg_qty: Decimal,
+    pub time: u64,
+    pub update_time: u64,
+    pub is_working: bool,
+    pub self_trade_prevention_mode: Option<String>,
+}
+
+impl From<&Order> for OrderInfo {
+    fn from(order: &Order) -> Self {
+        Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id,
+            order_list_id: None,
+            client_order_id: order.client_order_id.clone(),
+            transact_time: order.update_time,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            executed_qty: order.executed_qty,
+            cummulative_quote_qty: order.cummulative_quote_qty,
+            stop_price: order.stop_price,
+            status: order.status.clone(),
+            time_in_force: order.time_in_force.clone(),
+            type_name: order.type_name.clone(),
+            side: order.side.clone(),
+            fills: None,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod account;
+mod order;
+
+pub use account::*;
+pub use order::*;
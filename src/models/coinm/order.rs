@@ -0,0 +1,54 @@
+use crate::{
+    error::BinanceResponseError,
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A COIN-M order, which unlike USD-M orders is sized in contracts and has no quote amount.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cum_base: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub orig_type: String,
+    pub side: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub activate_price: Option<Decimal>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub price_rate: Option<Decimal>,
+    pub working_type: String,
+    pub price_protect: bool,
+    /// Only set when the order is queried.
+    pub time: Option<u64>,
+    pub update_time: u64,
+}
+
+/// Each order of a batch succeeds or fails on its own.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum BatchOrderResult {
+    Order(Box<Order>),
+    Error(BinanceResponseError),
+}
@@ -3,55 +3,95 @@ use crate::parser::string_or_decimal_opt;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+pub mod classic_margin;
+pub mod coinm;
+pub mod options;
 pub mod spot;
 pub mod usdm;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Product {
     Spot,
     UsdMFutures,
     CoinMFutures,
     EuropeanOptions,
     PortfolioMargin,
+    /// The `/sapi` wallet, margin and asset services. They share the spot host and endpoints
+    /// but are rate limited apart from the spot API.
+    Sapi,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+impl Product {
+    pub const ALL: [Product; 6] = [
+        Product::Spot,
+        Product::UsdMFutures,
+        Product::CoinMFutures,
+        Product::EuropeanOptions,
+        Product::PortfolioMargin,
+        Product::Sapi,
+    ];
+
+    /// Whether `endpoint` is on the REST host of this product, checked at compile time by
+    /// `define_request!`.
+    pub const fn serves(self, endpoint: &str) -> bool {
+        match self {
+            Product::Spot => starts_with(endpoint, "/api/"),
+            Product::UsdMFutures => {
+                starts_with(endpoint, "/fapi/") || starts_with(endpoint, "/futures/data/")
+            }
+            Product::CoinMFutures => {
+                starts_with(endpoint, "/dapi/") || starts_with(endpoint, "/futures/data/")
+            }
+            Product::EuropeanOptions => starts_with(endpoint, "/eapi/"),
+            Product::PortfolioMargin => starts_with(endpoint, "/papi/"),
+            Product::Sapi => starts_with(endpoint, "/sapi/"),
+        }
+    }
+}
+
+const fn starts_with(s: &str, prefix: &str) -> bool {
+    let (s, prefix) = (s.as_bytes(), prefix.as_bytes());
+    if s.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if s[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerTime {
     pub server_time: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Side {
+    #[default]
     Buy,
     Sell,
 }
 
-impl Default for Side {
-    fn default() -> Self {
-        Self::Buy
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum TimeInForce {
+    #[default]
     GTC,
     IOC,
     FOK,
     GTX,
 }
 
-impl Default for TimeInForce {
-    fn default() -> Self {
-        Self::GTC
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderType {
+    #[default]
     Market,
     Limit,
     StopLoss,
@@ -59,12 +99,11 @@ pub enum OrderType {
     TakeProfit,
     TakeProfitLimit,
     LimitMaker,
-}
-
-impl Default for OrderType {
-    fn default() -> Self {
-        OrderType::Market
-    }
+    // Futures only
+    Stop,
+    StopMarket,
+    TakeProfitMarket,
+    TrailingStopMarket,
 }
 
 // {
@@ -171,6 +210,9 @@ pub enum Filter {
         min_trailing_below_delta: Option<u16>,
         max_trailing_below_delta: Option<u16>,
     },
+    /// A filter not modelled here, kept so new filters don't break `exchangeInfo`.
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -216,24 +258,43 @@ pub enum ExecutionType {
 pub struct RateLimit {
     pub rate_limit_type: RateLimitType,
     pub interval: Interval,
+    #[serde(default = "default_interval_num")]
+    pub interval_num: u64,
     pub limit: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+fn default_interval_num() -> u64 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RateLimitType {
     Orders,
     RequestWeight,
+    RawRequests,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Interval {
     Second,
     Minute,
+    Hour,
     Day,
 }
 
+impl Interval {
+    pub fn millis(&self) -> i64 {
+        match self {
+            Interval::Second => 1000,
+            Interval::Minute => 60 * 1000,
+            Interval::Hour => 60 * 60 * 1000,
+            Interval::Day => 24 * 60 * 60 * 1000,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetInformation {
@@ -241,3 +302,18 @@ pub struct AssetInformation {
     margin_available: bool,
     auto_asset_exchange: String,
 }
+
+#[cfg(test)]
+mod test {
+    use super::Product;
+
+    #[test]
+    fn product_serves() {
+        assert!(Product::Sapi.serves("/sapi/v1/capital/config/getall"));
+        assert!(!Product::Spot.serves("/sapi/v1/capital/config/getall"));
+        assert!(Product::CoinMFutures.serves("/dapi/v1/order"));
+        assert!(Product::UsdMFutures.serves("/futures/data/basis"));
+        assert!(!Product::UsdMFutures.serves("/dapi/v1/order"));
+        assert!(!Product::PortfolioMargin.serves("/pap"));
+    }
+}
@@ -0,0 +1,58 @@
+use super::{Greeks, OptionSide, PositionSide};
+use crate::parser::string_or_decimal;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Asset {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub margin_balance: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub equity: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub available: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub locked: Decimal,
+    #[serde(rename = "unrealizedPNL", with = "string_or_decimal")]
+    pub unrealized_pnl: Decimal,
+}
+
+/// The aggregated greeks of all positions on `underlying`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnderlyingGreeks {
+    pub underlying: String,
+    #[serde(flatten)]
+    pub greeks: Greeks,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub symbol: String,
+    pub side: PositionSide,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quantity: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub reducible_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_value: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub ror: Decimal,
+    #[serde(rename = "unrealizedPNL", with = "string_or_decimal")]
+    pub unrealized_pnl: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub strike_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub position_cost: Decimal,
+    pub expiry_date: u64,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub option_side: OptionSide,
+    pub quote_asset: String,
+}
@@ -0,0 +1,114 @@
+mod account;
+mod order;
+
+pub use account::*;
+pub use order::*;
+
+use crate::{
+    error::BinanceError::{self, InvalidOptionSymbol},
+    parser::string_or_decimal,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OptionSide {
+    Call,
+    Put,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PositionSide {
+    Long,
+    Short,
+}
+
+/// The strike, expiry and side encoded in an option symbol such as `BTC-230630-30000-C`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionSymbol {
+    pub base_asset: String,
+    pub expiry: NaiveDate,
+    pub strike: Decimal,
+    pub side: OptionSide,
+}
+
+impl FromStr for OptionSymbol {
+    type Err = BinanceError;
+
+    fn from_str(symbol: &str) -> Result<Self, BinanceError> {
+        let invalid = || InvalidOptionSymbol(symbol.into());
+        let parts: Vec<&str> = symbol.split('-').collect();
+        let (base_asset, expiry, strike, side) = match parts[..] {
+            [base_asset, expiry, strike, side] => (base_asset, expiry, strike, side),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            base_asset: base_asset.into(),
+            expiry: NaiveDate::parse_from_str(expiry, "%y%m%d").map_err(|_| invalid())?,
+            strike: strike.parse().map_err(|_| invalid())?,
+            side: match side {
+                "C" => OptionSide::Call,
+                "P" => OptionSide::Put,
+                _ => return Err(invalid()),
+            },
+        })
+    }
+}
+
+impl fmt::Display for OptionSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = match self.side {
+            OptionSide::Call => 'C',
+            OptionSide::Put => 'P',
+        };
+        write!(
+            f,
+            "{}-{}-{}-{}",
+            self.base_asset,
+            self.expiry.format("%y%m%d"),
+            self.strike.normalize(),
+            side
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Greeks {
+    #[serde(with = "string_or_decimal")]
+    pub delta: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub gamma: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub theta: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub vega: Decimal,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{OptionSide, OptionSymbol};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn option_symbol() {
+        let symbol: OptionSymbol = "BTC-230630-30000-C".parse().unwrap();
+        assert_eq!(symbol.base_asset, "BTC");
+        assert_eq!(symbol.expiry, NaiveDate::from_ymd_opt(2023, 6, 30).unwrap());
+        assert_eq!(symbol.strike, Decimal::from(30000));
+        assert_eq!(symbol.side, OptionSide::Call);
+        assert_eq!(symbol.to_string(), "BTC-230630-30000-C");
+
+        let symbol: OptionSymbol = "DOGE-230630-0.07-P".parse().unwrap();
+        assert_eq!(symbol.strike, "0.07".parse().unwrap());
+        assert_eq!(symbol.to_string(), "DOGE-230630-0.07-P");
+
+        assert!("BTCUSDT".parse::<OptionSymbol>().is_err());
+        assert!("BTC-230630-30000-X".parse::<OptionSymbol>().is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use super::OptionSide;
+use crate::{
+    error::BinanceResponseError,
+    models::Side,
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_id: u64,
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quantity: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub fee: Decimal,
+    pub side: Side,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub time_in_force: String,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub create_time: u64,
+    pub update_time: u64,
+    /// `ACCEPTED`, `REJECTED`, `PARTIALLY_FILLED`, `FILLED` or `CANCELLED`.
+    pub status: String,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub avg_price: Option<Decimal>,
+    pub client_order_id: Option<String>,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub option_side: OptionSide,
+    pub quote_asset: String,
+    #[serde(default)]
+    pub mmp: bool,
+}
+
+/// Each order of a batch succeeds or fails on its own.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum BatchOrderResult {
+    Order(Box<Order>),
+    Error(BinanceResponseError),
+}
@@ -9,27 +9,17 @@ pub struct RateLimit {
     rate_limit_type: RateLimitType,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Interval {
+    #[default]
     Minute,
 }
 
-impl Default for Interval {
-    fn default() -> Self {
-        Interval::Minute
-    }
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RateLimitType {
+    #[default]
     RequestWeight,
     Orders,
 }
-
-impl Default for RateLimitType {
-    fn default() -> Self {
-        RateLimitType::RequestWeight
-    }
-}
@@ -4,7 +4,7 @@ mod order;
 pub use self::{market::*, order::*};
 use crate::parser::string_or_decimal;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -54,7 +54,7 @@ pub struct Bids {
     pub qty: Decimal,
 
     // Never serialized.
-    #[serde(skip_serializing, rename = "ignore")]
+    #[serde(skip_serializing, default, rename = "ignore")]
     _ignore: Vec<String>,
 }
 
@@ -66,7 +66,7 @@ pub struct Asks {
     pub qty: Decimal,
 
     // Never serialized.
-    #[serde(skip_serializing, rename = "ignore")]
+    #[serde(skip_serializing, default, rename = "ignore")]
     _ignore: Vec<String>,
 }
 
@@ -84,6 +84,7 @@ pub struct Success {}
 #[serde(untagged)]
 pub enum Prices {
     AllPrices(Vec<SymbolPrice>),
+    SinglePrice(SymbolPrice),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -98,6 +99,7 @@ pub struct SymbolPrice {
 #[serde(untagged)]
 pub enum BookTickers {
     AllBookTickers(Vec<Ticker>),
+    SingleBookTicker(Ticker),
 }
 
 #[derive(Debug, Clone)]
@@ -130,6 +132,8 @@ pub struct TradeHistory {
     #[serde(with = "string_or_decimal")]
     pub qty: Decimal,
     #[serde(with = "string_or_decimal")]
+    pub quote_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
     pub commission: Decimal,
     pub commission_asset: String,
     pub time: u64,
@@ -171,6 +175,89 @@ pub struct PriceStats {
     pub count: u64,
 }
 
+/// The 24hr ticker, a single `PriceStats` when queried with `symbol`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum PriceStatistics {
+    AllPriceStats(Vec<PriceStats>),
+    SinglePriceStats(Box<PriceStats>),
+}
+
+/// The statistics of the rolling window and trading day tickers, which unlike `PriceStats`
+/// carry no order book or previous close.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowStats {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub price_change: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub price_change_percent: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub weighted_avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub last_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub volume: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quote_volume: Decimal,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub first_id: i64,
+    pub last_id: i64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum WindowStatistics {
+    AllWindowStats(Vec<WindowStats>),
+    SingleWindowStats(Box<WindowStats>),
+}
+
+/// A public trade of `/api/v3/trades` and `/api/v3/historicalTrades`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTrade {
+    pub id: u64,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quote_qty: Decimal,
+    pub time: u64,
+    pub is_buyer_maker: bool,
+    pub is_best_match: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AggTrade {
+    #[serde(rename = "a")]
+    pub agg_trade_id: u64,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,
+    #[serde(rename = "T")]
+    pub time: u64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+    /// Always `false` for futures, which don't report it.
+    #[serde(rename = "M", default)]
+    pub is_best_match: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct KlineSummary {
     pub open_time: i64,
@@ -196,6 +283,45 @@ pub struct KlineSummary {
     pub taker_buy_quote_asset_volume: Decimal,
 }
 
+/// Klines are arrays rather than objects, the trailing field is unused.
+#[derive(Deserialize)]
+struct RawKlineSummary(
+    i64,
+    #[serde(with = "string_or_decimal")] Decimal,
+    #[serde(with = "string_or_decimal")] Decimal,
+    #[serde(with = "string_or_decimal")] Decimal,
+    #[serde(with = "string_or_decimal")] Decimal,
+    #[serde(with = "string_or_decimal")] Decimal,
+    i64,
+    #[serde(with = "string_or_decimal")] Decimal,
+    i64,
+    #[serde(with = "string_or_decimal")] Decimal,
+    #[serde(with = "string_or_decimal")] Decimal,
+    serde::de::IgnoredAny,
+);
+
+impl<'de> Deserialize<'de> for KlineSummary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawKlineSummary::deserialize(deserializer)?;
+        Ok(Self {
+            open_time: raw.0,
+            open: raw.1,
+            high: raw.2,
+            low: raw.3,
+            close: raw.4,
+            volume: raw.5,
+            close_time: raw.6,
+            quote_asset_volume: raw.7,
+            number_of_trades: raw.8,
+            taker_buy_base_asset_volume: raw.9,
+            taker_buy_quote_asset_volume: raw.10,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Kline {
@@ -262,8 +388,14 @@ pub struct Kline {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "filterType", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ExchangeFilter {
-    ExchangeMaxNumOrders { limit: u64 },
-    ExchangeMaxAlgoOrders { limit: u64 },
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumOrders { max_num_orders: u64 },
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumAlgoOrders { max_num_algo_orders: u64 },
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumIcebergOrders { max_num_iceberg_orders: u64 },
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumOrderLists { max_num_order_lists: u64 },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -279,6 +411,33 @@ pub struct OrderBook {
 pub enum NewOrderResponseType {
     Ack,
     Result,
+    /// Spot only
+    Full,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradePreventionMode {
+    None,
+    ExpireTaker,
+    ExpireMaker,
+    ExpireBoth,
+    Decrement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceMode {
+    #[default]
+    StopOnFailure,
+    AllowFailure,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelRestrictions {
+    OnlyNew,
+    OnlyPartiallyFilled,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -292,3 +451,25 @@ pub enum OrderExecType {
 pub enum OrderRejectReason {
     None,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{KlineSummary, Prices};
+    use serde_json::from_str;
+
+    #[test]
+    fn market_data_shapes() {
+        let klines = r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]]"#;
+        let klines: Vec<KlineSummary> = from_str(klines).unwrap();
+        assert_eq!(klines[0].close_time, 1499644799999);
+        assert_eq!(klines[0].number_of_trades, 308);
+
+        let single = r#"{"symbol":"LTCBTC","price":"4.00000200"}"#;
+        let all = r#"[{"symbol":"LTCBTC","price":"4.00000200"},{"symbol":"ETHBTC","price":"0.07946600"}]"#;
+        assert!(matches!(from_str(single).unwrap(), Prices::SinglePrice(_)));
+        match from_str(all).unwrap() {
+            Prices::AllPrices(prices) => assert_eq!(prices.len(), 2),
+            prices => panic!("unexpected {:?}", prices),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::parser::{string_or_decimal, string_or_decimal_opt};
+use crate::{
+    models::usdm,
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -43,6 +46,35 @@ pub struct NewOrder {
     price_protect: bool,
 }
 
+impl From<&usdm::Order> for NewOrder {
+    fn from(order: &usdm::Order) -> Self {
+        Self {
+            client_order_id: order.client_order_id.clone(),
+            cum_qty: order.executed_qty,
+            cum_quote: order.cum_quote,
+            executed_qty: order.executed_qty,
+            order_id: order.order_id,
+            avg_price: order.avg_price,
+            orig_qty: order.orig_qty,
+            reduce_only: order.reduce_only,
+            side: order.side.clone(),
+            position_side: order.position_side.clone(),
+            status: order.status.clone(),
+            stop_price: order.stop_price,
+            close_position: order.close_position,
+            symbol: order.symbol.clone(),
+            time_in_force: order.time_in_force.clone(),
+            type_name: order.type_name.clone(),
+            orig_type: order.orig_type.clone(),
+            activate_price: order.activate_price,
+            price_rate: order.price_rate,
+            update_time: order.update_time,
+            working_type: order.working_type.clone(),
+            price_protect: order.price_protect,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderInfo {
@@ -123,3 +155,160 @@ pub struct CanceledOrder {
     pub working_type: String,
     price_protect: bool,
 }
+
+/// The response to a spot order placement, whose shape follows `newOrderRespType`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum OrderResponse {
+    /// `RESULT` and `FULL`, only the latter fills in `fills`.
+    Result(Box<OrderInfo>),
+    Ack(OrderAck),
+}
+
+impl OrderResponse {
+    pub fn order_id(&self) -> u64 {
+        match self {
+            Self::Result(info) => info.order_id,
+            Self::Ack(ack) => ack.order_id,
+        }
+    }
+
+    pub fn client_order_id(&self) -> &str {
+        match self {
+            Self::Result(info) => &info.client_order_id,
+            Self::Ack(ack) => &ack.client_order_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAck {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: u64,
+}
+
+impl From<&Order> for OrderInfo {
+    fn from(order: &Order) -> Self {
+        Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id,
+            order_list_id: Some(order.order_list_id),
+            client_order_id: order.client_order_id.clone(),
+            transact_time: order.update_time,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            executed_qty: order.executed_qty,
+            cummulative_quote_qty: order.cummulative_quote_qty,
+            stop_price: order.stop_price,
+            status: order.status.clone(),
+            time_in_force: order.time_in_force.clone(),
+            type_name: order.type_name.clone(),
+            side: order.side.clone(),
+            fills: None,
+        }
+    }
+}
+
+/// A spot order as returned by the order queries.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub order_id: u64,
+    /// `-1` unless the order is part of an order list.
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cummulative_quote_qty: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub side: String,
+    #[serde(with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub iceberg_qty: Decimal,
+    pub time: u64,
+    pub update_time: u64,
+    pub is_working: bool,
+    pub working_time: Option<u64>,
+    #[serde(with = "string_or_decimal")]
+    pub orig_quote_order_qty: Decimal,
+    pub self_trade_prevention_mode: Option<String>,
+}
+
+/// An OCO, OTO or OTOCO order list.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: u64,
+    pub contingency_type: String,
+    pub list_status_type: String,
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OrderListEntry>,
+    /// Empty for `ACK` responses.
+    #[serde(default)]
+    pub order_reports: Vec<OrderInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListEntry {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+}
+
+/// A match that self-trade prevention stopped from happening.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatch {
+    pub symbol: String,
+    pub prevented_match_id: u64,
+    pub taker_order_id: u64,
+    pub maker_order_id: u64,
+    pub trade_group_id: u64,
+    pub self_trade_prevention_mode: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maker_prevented_quantity: Decimal,
+    pub transact_time: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::OrderResponse;
+    use serde_json::from_str;
+
+    #[test]
+    fn order_response_types() {
+        let ack = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595}"#;
+        let result = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595,"price":"0.00000000","origQty":"10.00000000","executedQty":"10.00000000","cummulativeQuoteQty":"10.00000000","status":"FILLED","timeInForce":"GTC","type":"MARKET","side":"SELL","workingTime":1507725176595,"selfTradePreventionMode":"NONE"}"#;
+        let full = r#"{"symbol":"BTCUSDT","orderId":28,"orderListId":-1,"clientOrderId":"6gCrw2kRUAF9CvJDGP16IP","transactTime":1507725176595,"price":"0.00000000","origQty":"10.00000000","executedQty":"10.00000000","cummulativeQuoteQty":"10.00000000","status":"FILLED","timeInForce":"GTC","type":"MARKET","side":"SELL","workingTime":1507725176595,"selfTradePreventionMode":"NONE","fills":[{"price":"4000.00000000","qty":"1.00000000","commission":"4.00000000","commissionAsset":"USDT","tradeId":56}]}"#;
+
+        assert!(matches!(from_str(ack).unwrap(), OrderResponse::Ack(_)));
+        match from_str(result).unwrap() {
+            OrderResponse::Result(info) => assert!(info.fills.is_none()),
+            resp => panic!("{:?}", resp),
+        }
+        match from_str(full).unwrap() {
+            OrderResponse::Result(info) => assert_eq!(info.fills.unwrap().len(), 1),
+            resp => panic!("{:?}", resp),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod account;
+mod order;
 
 pub use account::*;
+pub use order::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -17,3 +19,11 @@ pub enum WorkingType {
     MarkPrice,
     ContractPrice,
 }
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarginType {
+    Isolated,
+    #[default]
+    Crossed,
+}
@@ -0,0 +1,72 @@
+use crate::{
+    error::BinanceResponseError,
+    models::spot::NewOrder,
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cum_quote: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub orig_type: String,
+    pub side: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub activate_price: Option<Decimal>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub price_rate: Option<Decimal>,
+    pub working_type: String,
+    pub price_protect: bool,
+    pub time: u64,
+    pub update_time: u64,
+}
+
+/// Each order of a batch succeeds or fails on its own.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum BatchOrderResult {
+    Order(Box<NewOrder>),
+    Error(BinanceResponseError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::BatchOrderResult;
+    use serde_json::from_str;
+
+    #[test]
+    fn batch_order_results() {
+        let results = r#"[{"clientOrderId":"testOrder","cumQty":"0","cumQuote":"0","executedQty":"0","orderId":22542179,"avgPrice":"0.00000","origQty":"10","price":"0","reduceOnly":false,"side":"BUY","positionSide":"SHORT","status":"NEW","stopPrice":"9300","closePosition":false,"symbol":"BTCUSDT","timeInForce":"GTC","type":"TRAILING_STOP_MARKET","origType":"TRAILING_STOP_MARKET","activatePrice":"9020","priceRate":"0.3","updateTime":1566818724722,"workingType":"CONTRACT_PRICE","priceProtect":false},{"code":-2022,"msg":"ReduceOnly Order is rejected."}]"#;
+        let results: Vec<BatchOrderResult> = from_str(results).unwrap();
+        match &results[0] {
+            BatchOrderResult::Order(order) => assert_eq!(order.order_id, 22542179),
+            result => panic!("unexpected {:?}", result),
+        }
+        match &results[1] {
+            BatchOrderResult::Error(error) => assert_eq!(error.code, -2022),
+            result => panic!("unexpected {:?}", result),
+        }
+    }
+}
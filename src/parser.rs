@@ -37,7 +37,7 @@ pub mod string_or_decimal {
 
 pub mod string_or_decimal_opt {
     use rust_decimal::Decimal;
-    use serde::{Deserializer, Serializer};
+    use serde::{de, Deserialize, Deserializer, Serializer};
     use std::fmt;
 
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
@@ -51,11 +51,25 @@ pub mod string_or_decimal_opt {
         }
     }
 
+    /// `null` and empty strings are `None`.
     pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        Ok(Some(super::string_or_decimal::deserialize(deserializer)?))
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrFloat {
+            String(String),
+            Float(Decimal),
+        }
+
+        match Option::<StringOrFloat>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(StringOrFloat::String(s)) if s.is_empty() => Ok(None),
+            Some(StringOrFloat::String(s)) if s == "INF" => Ok(Some(Decimal::MAX)),
+            Some(StringOrFloat::String(s)) => s.parse().map(Some).map_err(de::Error::custom),
+            Some(StringOrFloat::Float(i)) => Ok(Some(i)),
+        }
     }
 }
 
@@ -90,3 +104,79 @@ pub mod string_or {
         }
     }
 }
+
+/// A list parameter that Binance expects as a JSON encoded string, e.g. `batchOrders`.
+pub mod json_string {
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let json = serde_json::to_string(value).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&json)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: serde::de::DeserializeOwned,
+    {
+        let json = String::deserialize(deserializer)?;
+        serde_json::from_str(&json).map_err(de::Error::custom)
+    }
+}
+
+/// A list parameter that Binance expects comma separated, e.g. the `asset` of a dust transfer.
+pub mod comma_separated {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&value.join(","))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.split(',')
+            .filter(|s| !s.is_empty())
+            .map(Into::into)
+            .collect())
+    }
+}
+
+/// A flag that Binance expects as `TRUE` or `FALSE`, e.g. the `isIsolated` of margin requests.
+pub mod uppercase_bool {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(true) => serializer.serialize_str("TRUE"),
+            Some(false) => serializer.serialize_str("FALSE"),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => s
+                .to_lowercase()
+                .parse()
+                .map(Some)
+                .map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
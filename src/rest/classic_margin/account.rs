@@ -0,0 +1,310 @@
+use crate::models::{classic_margin::BorrowRepayType, Product};
+use crate::parser::{comma_separated, string_or_decimal, string_or_decimal_opt, uppercase_bool};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+crate::define_request! {
+    Name => BorrowRepay;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/margin/borrow-repay";
+    Signed => true;
+    Weight => 1500;
+    UidWeight => true;
+    Request => {
+        pub asset: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        /// Only for isolated margin.
+        pub symbol: Option<String>,
+        pub amount: Decimal,
+        pub r#type: BorrowRepayType,
+    };
+    Response => {
+        pub tran_id: u64,
+    };
+}
+
+crate::define_request! {
+    Name => BorrowRepayRecords;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/borrow-repay";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        pub r#type: BorrowRepayType,
+        pub asset: Option<String>,
+        pub isolated_symbol: Option<String>,
+        pub tx_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        /// From 1.
+        pub current: Option<u64>,
+        pub size: Option<u64>,
+    };
+    Response => {
+        pub total: u64,
+        pub rows: Vec<BorrowRepayRecord>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BorrowRepayRecord {
+    /// Only set for isolated margin.
+    pub isolated_symbol: Option<String>,
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub principal: Decimal,
+    /// Only set for repayments.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub interest: Option<Decimal>,
+    /// `PENDING`, `CONFIRMED` or `FAILED`.
+    pub status: String,
+    pub timestamp: u64,
+    pub tx_id: u64,
+}
+
+crate::define_request! {
+    Name => MarginAccount;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/account";
+    Signed => true;
+    Weight => 10;
+    Request => {};
+    Response => {
+        pub borrow_enabled: bool,
+        pub trade_enabled: bool,
+        pub transfer_in_enabled: bool,
+        pub transfer_out_enabled: bool,
+        #[serde(with = "string_or_decimal")]
+        pub margin_level: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub collateral_margin_level: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_asset_of_btc: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_liability_of_btc: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_net_asset_of_btc: Decimal,
+        #[serde(rename = "TotalCollateralValueInUSDT", with = "string_or_decimal")]
+        pub total_collateral_value_in_usdt: Decimal,
+        /// `MARGIN_1` for classic margin or `MARGIN_2` for the pro mode.
+        pub account_type: String,
+        pub user_assets: Vec<MarginAsset>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAsset {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub free: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub locked: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub borrowed: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub net_asset: Decimal,
+}
+
+crate::define_request! {
+    Name => IsolatedMarginAccount;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/isolated/account";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        /// At most 5 symbols, every isolated account by default.
+        #[serde(with = "comma_separated", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+    };
+    Response => {
+        pub assets: Vec<IsolatedMarginPair>,
+        /// The totals are only set when `symbols` is empty.
+        #[serde(default, with = "string_or_decimal_opt")]
+        pub total_asset_of_btc: Option<Decimal>,
+        #[serde(default, with = "string_or_decimal_opt")]
+        pub total_liability_of_btc: Option<Decimal>,
+        #[serde(default, with = "string_or_decimal_opt")]
+        pub total_net_asset_of_btc: Option<Decimal>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginPair {
+    pub symbol: String,
+    pub base_asset: IsolatedMarginAsset,
+    pub quote_asset: IsolatedMarginAsset,
+    pub isolated_created: bool,
+    pub enabled: bool,
+    #[serde(with = "string_or_decimal")]
+    pub margin_level: Decimal,
+    /// `EXCESSIVE`, `NORMAL`, `MARGIN_CALL`, `PRE_LIQUIDATION` or `FORCE_LIQUIDATION`.
+    pub margin_level_status: String,
+    #[serde(with = "string_or_decimal")]
+    pub margin_ratio: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub index_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidate_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidate_rate: Decimal,
+    pub trade_enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAsset {
+    pub asset: String,
+    pub borrow_enabled: bool,
+    pub repay_enabled: bool,
+    #[serde(with = "string_or_decimal")]
+    pub free: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub locked: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub borrowed: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub net_asset: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub net_asset_of_btc: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub total_asset: Decimal,
+}
+
+crate::define_request! {
+    Name => MaxBorrowable;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/maxBorrowable";
+    Signed => true;
+    Weight => 50;
+    Request => {
+        pub asset: String,
+        pub isolated_symbol: Option<String>,
+    };
+    Response => {
+        #[serde(with = "string_or_decimal")]
+        pub amount: Decimal,
+        /// The limit of the account's VIP level.
+        #[serde(with = "string_or_decimal")]
+        pub borrow_limit: Decimal,
+    };
+}
+
+crate::define_request! {
+    Name => MaxTransferable;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/maxTransferable";
+    Signed => true;
+    Weight => 50;
+    Request => {
+        pub asset: String,
+        pub isolated_symbol: Option<String>,
+    };
+    Response => {
+        #[serde(with = "string_or_decimal")]
+        pub amount: Decimal,
+    };
+}
+
+crate::define_request! {
+    Name => InterestHistory;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/interestHistory";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub asset: Option<String>,
+        pub isolated_symbol: Option<String>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub current: Option<u64>,
+        pub size: Option<u64>,
+    };
+    Response => {
+        pub total: u64,
+        pub rows: Vec<Interest>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Interest {
+    pub tx_id: u64,
+    pub interest_accured_time: u64,
+    pub asset: String,
+    /// The borrowed asset when `asset` is the BNB the interest was paid with.
+    pub raw_asset: Option<String>,
+    #[serde(with = "string_or_decimal")]
+    pub principal: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub interest_rate: Decimal,
+    /// `PERIODIC`, `ON_BORROW`, `PERIODIC_CONVERTED` or `ON_BORROW_CONVERTED`.
+    pub r#type: String,
+    pub isolated_symbol: Option<String>,
+}
+
+crate::define_request! {
+    Name => ForceLiquidationRecords;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/forceLiquidationRec";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub isolated_symbol: Option<String>,
+        pub current: Option<u64>,
+        pub size: Option<u64>,
+    };
+    Response => {
+        pub total: u64,
+        pub rows: Vec<ForceLiquidation>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceLiquidation {
+    pub symbol: String,
+    pub is_isolated: bool,
+    pub order_id: u64,
+    pub side: String,
+    pub time_in_force: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    pub updated_time: u64,
+}
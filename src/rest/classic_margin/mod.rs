@@ -0,0 +1,7 @@
+mod account;
+mod trade;
+mod user_stream;
+
+pub use account::*;
+pub use trade::*;
+pub use user_stream::*;
@@ -0,0 +1,246 @@
+use crate::models::{
+    classic_margin::{Order, SideEffectType},
+    spot::{NewOrderResponseType, OrderList, OrderResponse, SelfTradePreventionMode},
+    OrderType, Product, Side, TimeInForce,
+};
+use crate::parser::{string_or_decimal, uppercase_bool};
+use crate::rest::ConfirmableOrder;
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+
+crate::define_request! {
+    Name => NewOrder;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/margin/order";
+    Signed => true;
+    Weight => 6;
+    IsOrder => true;
+    UidWeight => true;
+    Request => {
+        pub symbol: String,
+        /// Cross margin unless set.
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub side: Side,
+        pub r#type: OrderType,
+        pub quantity: Option<Decimal>,
+        pub quote_order_qty: Option<Decimal>,
+        pub price: Option<Decimal>,
+        pub stop_price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub iceberg_qty: Option<Decimal>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub side_effect_type: Option<SideEffectType>,
+        pub time_in_force: Option<TimeInForce>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        /// Repay the borrowed assets when an `AUTO_BORROW_REPAY` or `MARGIN_BUY` order is
+        /// canceled, `true` by default.
+        pub auto_repay_at_cancel: Option<bool>,
+    };
+    Response => OrderResponse;
+}
+
+impl ConfirmableOrder for NewOrderRequest {
+    type Query = QueryOrderRequest;
+
+    fn query(&self) -> Option<QueryOrderRequest> {
+        Some(QueryOrderRequest {
+            symbol: self.symbol.clone(),
+            is_isolated: self.is_isolated,
+            order_id: None,
+            orig_client_order_id: Some(self.new_client_order_id.clone()?),
+        })
+    }
+
+    fn confirmed(&self, order: &Order) -> OrderResponse {
+        OrderResponse::Result(Box::new(order.into()))
+    }
+}
+
+crate::define_request! {
+    Name => QueryOrder;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/order";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        pub symbol: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelOrder;
+    Product => Product::Sapi;
+    Method => Method::DELETE;
+    Endpoint => "/sapi/v1/margin/order";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        pub symbol: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+        pub new_client_order_id: Option<String>,
+    };
+    Response => {
+        pub symbol: String,
+        pub is_isolated: bool,
+        pub order_id: u64,
+        pub orig_client_order_id: String,
+        pub client_order_id: String,
+        #[serde(with = "string_or_decimal")]
+        pub price: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub orig_qty: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub executed_qty: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub cummulative_quote_qty: Decimal,
+        pub status: String,
+        pub time_in_force: String,
+        #[serde(rename = "type")]
+        pub type_name: String,
+        pub side: String,
+    };
+}
+
+crate::define_request! {
+    Name => OpenOrders;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/openOrders";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        /// Required for isolated margin.
+        pub symbol: Option<String>,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => AllOrders;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/margin/allOrders";
+    Signed => true;
+    Weight => 200;
+    Request => {
+        pub symbol: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => NewOco;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/margin/order/oco";
+    Signed => true;
+    Weight => 6;
+    IsOrder => true;
+    UidWeight => true;
+    Request => {
+        pub symbol: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub list_client_order_id: Option<String>,
+        pub side: Side,
+        pub quantity: Decimal,
+        pub limit_client_order_id: Option<String>,
+        pub price: Decimal,
+        pub limit_iceberg_qty: Option<Decimal>,
+        pub stop_client_order_id: Option<String>,
+        pub stop_price: Decimal,
+        /// Makes the stop leg a `STOP_LOSS_LIMIT` order, which then needs `stop_limit_time_in_force`.
+        pub stop_limit_price: Option<Decimal>,
+        pub stop_iceberg_qty: Option<Decimal>,
+        pub stop_limit_time_in_force: Option<TimeInForce>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub side_effect_type: Option<SideEffectType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        pub auto_repay_at_cancel: Option<bool>,
+    };
+    Response => OrderList;
+}
+
+crate::define_request! {
+    Name => CancelOco;
+    Product => Product::Sapi;
+    Method => Method::DELETE;
+    Endpoint => "/sapi/v1/margin/orderList";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        #[serde(default, with = "uppercase_bool", skip_serializing_if = "Option::is_none")]
+        pub is_isolated: Option<bool>,
+        pub order_list_id: Option<u64>,
+        pub list_client_order_id: Option<String>,
+        pub new_client_order_id: Option<String>,
+    };
+    Response => OrderList;
+}
+
+#[cfg(test)]
+mod test {
+    use super::NewOrderRequest;
+    use crate::models::{OrderType, Side};
+    use rust_decimal::Decimal;
+
+    fn order(is_isolated: Option<bool>) -> NewOrderRequest {
+        NewOrderRequest {
+            symbol: "BTCUSDT".into(),
+            is_isolated,
+            side: Side::Buy,
+            r#type: OrderType::Market,
+            quantity: Some(Decimal::new(1, 3)),
+            quote_order_qty: None,
+            price: None,
+            stop_price: None,
+            new_client_order_id: None,
+            iceberg_qty: None,
+            new_order_resp_type: None,
+            side_effect_type: None,
+            time_in_force: None,
+            self_trade_prevention_mode: None,
+            auto_repay_at_cancel: None,
+        }
+    }
+
+    #[test]
+    fn new_order_params() {
+        assert_eq!(
+            serde_qs::to_string(&order(Some(true))).unwrap(),
+            "symbol=BTCUSDT&isIsolated=TRUE&side=BUY&type=MARKET&quantity=0.001"
+        );
+        assert_eq!(
+            serde_qs::to_string(&order(Some(false))).unwrap(),
+            "symbol=BTCUSDT&isIsolated=FALSE&side=BUY&type=MARKET&quantity=0.001"
+        );
+        assert_eq!(
+            serde_qs::to_string(&order(None)).unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=MARKET&quantity=0.001"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::models::{spot::UserDataStream, Product};
+use reqwest::Method;
+
+crate::define_request! {
+    Name => StartUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/userDataStream";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => UserDataStream;
+}
+
+crate::define_request! {
+    Name => KeepaliveUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::PUT;
+    Endpoint => "/sapi/v1/userDataStream";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => { pub listen_key: String };
+    Response => {};
+}
+
+crate::define_request! {
+    Name => CloseUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::DELETE;
+    Endpoint => "/sapi/v1/userDataStream";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => { pub listen_key: String };
+    Response => {};
+}
+
+crate::define_request! {
+    Name => StartIsolatedUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/userDataStream/isolated";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => { pub symbol: String };
+    Response => UserDataStream;
+}
+
+crate::define_request! {
+    Name => KeepaliveIsolatedUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::PUT;
+    Endpoint => "/sapi/v1/userDataStream/isolated";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub listen_key: String,
+    };
+    Response => {};
+}
+
+crate::define_request! {
+    Name => CloseIsolatedUserDataStream;
+    Product => Product::Sapi;
+    Method => Method::DELETE;
+    Endpoint => "/sapi/v1/userDataStream/isolated";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub listen_key: String,
+    };
+    Response => {};
+}
@@ -0,0 +1,239 @@
+use crate::models::coinm::{Asset, Position};
+use crate::models::usdm::{MarginType, PositionSide};
+use crate::models::Product;
+use crate::parser::{string_or, string_or_decimal};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+crate::define_request! {
+    Name => AccountInformation;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/account";
+    Signed => true;
+    Weight => 5;
+    Request => {};
+    Response => {
+        pub fee_tier: u64,
+        pub can_trade: bool,
+        pub can_deposit: bool,
+        pub can_withdraw: bool,
+        pub update_time: u64,
+        pub assets: Vec<Asset>,
+        pub positions: Vec<Position>,
+    };
+}
+
+crate::define_request! {
+    Name => AccountBalance;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/balance";
+    Signed => true;
+    Weight => 1;
+    Request => {};
+    Response => Vec<Balance>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub account_alias: String,
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub balance: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdraw_available: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_wallet_balance: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_un_pnl: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub available_balance: Decimal,
+    pub update_time: u64,
+}
+
+crate::define_request! {
+    Name => GetCurrentPositionMode;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/positionSide/dual";
+    Signed => true;
+    Weight => 30;
+    Request => {};
+    Response => {
+        pub dual_side_position: bool,
+    };
+}
+
+crate::define_request! {
+    Name => ChangePositionMode;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/positionSide/dual";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        /// `true` for the hedge mode, `false` for the one-way mode.
+        pub dual_side_position: bool,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => PositionRisk;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/positionRisk";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub margin_asset: Option<String>,
+        pub pair: Option<String>,
+    };
+    Response => Vec<PositionRisk>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRisk {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "unRealizedProfit", with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidation_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_qty: Decimal,
+    /// `isolated` or `cross`.
+    pub margin_type: String,
+    #[serde(with = "string_or_decimal")]
+    pub isolated_margin: Decimal,
+    #[serde(with = "string_or")]
+    pub is_auto_add_margin: bool,
+    #[serde(with = "string_or_decimal")]
+    pub notional_value: Decimal,
+    pub update_time: u64,
+}
+
+crate::define_request! {
+    Name => ChangeLeverage;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/leverage";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        /// From 1 to 125.
+        pub leverage: u64,
+    };
+    Response => {
+        pub symbol: String,
+        pub leverage: u64,
+        #[serde(with = "string_or_decimal")]
+        pub max_qty: Decimal,
+    };
+}
+
+crate::define_request! {
+    Name => ChangeMarginType;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/marginType";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub margin_type: MarginType,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => ModifyIsolatedPositionMargin;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/positionMargin";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub position_side: Option<PositionSide>,
+        pub amount: Decimal,
+        /// 1 to add margin, 2 to reduce it.
+        pub r#type: u8,
+    };
+    Response => {
+        #[serde(with = "string_or_decimal")]
+        pub amount: Decimal,
+        pub code: i64,
+        pub msg: String,
+        pub r#type: u8,
+    };
+}
+
+crate::define_request! {
+    Name => AccountTrades;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/userTrades";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 20 } else { 40 };
+    Request => {
+        /// Either `symbol` or `pair` must be set.
+        pub symbol: Option<String>,
+        pub pair: Option<String>,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub from_id: Option<u64>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<AccountTrade>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTrade {
+    pub id: u64,
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub side: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub base_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub realized_pnl: Decimal,
+    pub margin_asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub buyer: bool,
+    pub maker: bool,
+    pub time: u64,
+}
@@ -1,4 +1,4 @@
-use crate::models::{AssetInformation, Filter, Product, RateLimit};
+use crate::models::{AssetInformation, Filter, Product, RateLimit, ServerTime};
 use crate::parser::string_or_decimal;
 use chrono::serde::{ts_milliseconds, ts_milliseconds_option};
 use chrono::{DateTime, Utc};
@@ -6,12 +6,24 @@ use reqwest::Method;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+crate::define_request! {
+    Name => ServerTime;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/time";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => ServerTime;
+}
+
 crate::define_request! {
     Name => ExchangeInformation;
     Product => Product::CoinMFutures;
     Method => Method::GET;
     Endpoint => "/dapi/v1/exchangeInfo";
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {
         pub timezone: String,
@@ -41,6 +53,7 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/dapi/v1/fundingRate";
     Signed => false;
+    Weight => 1;
     Request => {
         pub symbol: String,
         #[serde(with = "ts_milliseconds_option")]
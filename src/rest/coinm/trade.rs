@@ -1,13 +1,70 @@
-use crate::models::{spot::NewOrderResponse, Product, Side};
+use crate::models::{
+    coinm::{BatchOrderResult, Order},
+    spot::NewOrderResponseType,
+    usdm::{PositionSide, WorkingType},
+    OrderType, Product, Side, TimeInForce,
+};
+use crate::parser::json_string;
+use crate::rest::ConfirmableOrder;
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
 use reqwest::Method;
 use rust_decimal::Decimal;
 
+crate::define_request! {
+    Name => NewOrder;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/order";
+    Signed => true;
+    Weight => 0;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub position_side: Option<PositionSide>,
+        pub r#type: OrderType,
+        pub time_in_force: Option<TimeInForce>,
+        /// In contracts.
+        pub quantity: Option<Decimal>,
+        pub reduce_only: Option<bool>,
+        pub price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub close_position: Option<bool>,
+        pub activation_price: Option<Decimal>,
+        pub callback_rate: Option<Decimal>,
+        pub working_type: Option<WorkingType>,
+        pub price_protect: Option<bool>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+    };
+    Response => Order;
+}
+
+impl ConfirmableOrder for NewOrderRequest {
+    type Query = QueryOrderRequest;
+
+    fn query(&self) -> Option<QueryOrderRequest> {
+        Some(QueryOrderRequest {
+            symbol: self.symbol.clone(),
+            order_id: None,
+            orig_client_order_id: Some(self.new_client_order_id.clone()?),
+        })
+    }
+
+    fn confirmed(&self, order: &Order) -> Order {
+        order.clone()
+    }
+}
+
 crate::define_request! {
     Name => ModifyOrder;
-    Product => Product::UsdMFutures;
+    Product => Product::CoinMFutures;
     Method => Method::PUT;
     Endpoint => "/dapi/v1/order";
     Signed => true;
+    Weight => 1;
+    IsOrder => true;
     Request => {
         pub order_id: Option<u64>,
         pub orig_client_order_id: Option<String>,
@@ -17,5 +74,104 @@ crate::define_request! {
         pub quantity: Option<Decimal>,
         pub price: Option<Decimal>,
     };
-    Response => NewOrderResponse;
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => QueryOrder;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelOrder;
+    Product => Product::CoinMFutures;
+    Method => Method::DELETE;
+    Endpoint => "/dapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelMultipleOrders;
+    Product => Product::CoinMFutures;
+    Method => Method::DELETE;
+    Endpoint => "/dapi/v1/batchOrders";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        /// At most 10 ids.
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub order_id_list: Vec<u64>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub orig_client_order_id_list: Vec<String>,
+    };
+    Response => Vec<BatchOrderResult>;
+}
+
+crate::define_request! {
+    Name => CancelAllOpenOrders;
+    Product => Product::CoinMFutures;
+    Method => Method::DELETE;
+    Endpoint => "/dapi/v1/allOpenOrders";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => OpenOrders;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/openOrders";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 1 } else { 40 };
+    Request => {
+        pub symbol: Option<String>,
+        pub pair: Option<String>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => AllOrders;
+    Product => Product::CoinMFutures;
+    Method => Method::GET;
+    Endpoint => "/dapi/v1/allOrders";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 20 } else { 40 };
+    Request => {
+        /// Either `symbol` or `pair` must be set.
+        pub symbol: Option<String>,
+        pub pair: Option<String>,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Order>;
 }
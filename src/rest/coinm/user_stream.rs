@@ -0,0 +1,40 @@
+use crate::rest::Product;
+use reqwest::Method;
+
+crate::define_request! {
+    Name => StartUserDataStream;
+    Product => Product::CoinMFutures;
+    Method => Method::POST;
+    Endpoint => "/dapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {
+        pub listen_key: String,
+    };
+}
+
+crate::define_request! {
+    Name => KeepaliveUserDataStream;
+    Product => Product::CoinMFutures;
+    Method => Method::PUT;
+    Endpoint => "/dapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
+
+crate::define_request! {
+    Name => CloseUserDataStream;
+    Product => Product::CoinMFutures;
+    Method => Method::DELETE;
+    Endpoint => "/dapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
@@ -0,0 +1,354 @@
+use crate::{
+    define_request,
+    models::Product,
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+define_request! {
+    Name => AccountInformation;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/account";
+    Signed => true;
+    Weight => 20;
+    Request => {};
+    Response => {
+        #[serde(rename = "uniMMR", with = "string_or_decimal")]
+        pub uni_mmr: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub account_equity: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub actual_equity: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub account_initial_margin: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub account_maint_margin: Decimal,
+        /// `NORMAL`, `MARGIN_CALL`, `SUPPLY_MARGIN`, `REDUCE_ONLY`, `ACTIVE_LIQUIDATION` or `FORCE_LIQUIDATION`.
+        pub account_status: String,
+        #[serde(with = "string_or_decimal")]
+        pub virtual_max_withdraw_amount: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_available_balance: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_margin_open_loss: Decimal,
+        pub update_time: u64,
+    };
+}
+
+define_request! {
+    Name => AccountBalance;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/balance";
+    Signed => true;
+    Weight => 20;
+    Request => {};
+    Response => Vec<Balance>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub total_wallet_balance: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_margin_asset: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_margin_borrowed: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_margin_free: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_margin_interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_margin_locked: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub um_wallet_balance: Decimal,
+    #[serde(rename = "umUnrealizedPNL", with = "string_or_decimal")]
+    pub um_unrealized_pnl: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cm_wallet_balance: Decimal,
+    #[serde(rename = "cmUnrealizedPNL", with = "string_or_decimal")]
+    pub cm_unrealized_pnl: Decimal,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub negative_balance: Option<Decimal>,
+    pub update_time: u64,
+}
+
+define_request! {
+    Name => UmAccountInformation;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/um/account";
+    Signed => true;
+    Weight => 5;
+    Request => {};
+    Response => {
+        pub assets: Vec<FuturesAsset>,
+        pub positions: Vec<UmAccountPosition>,
+    };
+}
+
+define_request! {
+    Name => CmAccountInformation;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/cm/account";
+    Signed => true;
+    Weight => 5;
+    Request => {};
+    Response => {
+        pub assets: Vec<FuturesAsset>,
+        pub positions: Vec<CmAccountPosition>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAsset {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub cross_wallet_balance: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cross_un_pnl: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maint_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub position_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub open_order_initial_margin: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UmAccountPosition {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maint_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub position_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub open_order_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_notional: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmAccountPosition {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maint_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub position_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub open_order_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_qty: Decimal,
+    pub update_time: u64,
+}
+
+define_request! {
+    Name => UmPositionRisk;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/um/positionRisk";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<UmPositionRisk>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UmPositionRisk {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "unRealizedProfit", with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidation_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_notional_value: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub notional: Decimal,
+    pub update_time: u64,
+}
+
+define_request! {
+    Name => CmPositionRisk;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/cm/positionRisk";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub margin_asset: Option<String>,
+        pub pair: Option<String>,
+    };
+    Response => Vec<CmPositionRisk>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmPositionRisk {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "unRealizedProfit", with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidation_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub notional_value: Decimal,
+    pub update_time: u64,
+}
+
+define_request! {
+    Name => MarginLoan;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/marginLoan";
+    Signed => true;
+    Weight => 100;
+    Request => {
+        pub asset: String,
+        pub amount: Decimal,
+    };
+    Response => {
+        pub tran_id: u64,
+    };
+}
+
+define_request! {
+    Name => RepayLoan;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/repayLoan";
+    Signed => true;
+    Weight => 100;
+    Request => {
+        pub asset: String,
+        pub amount: Decimal,
+    };
+    Response => {
+        pub tran_id: u64,
+    };
+}
+
+define_request! {
+    Name => AutoCollection;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/auto-collection";
+    Signed => true;
+    Weight => 750;
+    Request => {};
+    Response => {
+        pub msg: String,
+    };
+}
+
+define_request! {
+    Name => AssetCollection;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/asset-collection";
+    Signed => true;
+    Weight => 30;
+    Request => {
+        pub asset: String,
+    };
+    Response => {
+        pub msg: String,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        CmAccountInformationResponse, CmPositionRisk, UmAccountInformationResponse, UmPositionRisk,
+    };
+    use rust_decimal::Decimal;
+    use serde_json::from_str;
+
+    #[test]
+    fn account_information() {
+        let um = r#"{"assets":[{"asset":"USDT","crossWalletBalance":"23.72469206","crossUnPnl":"0.00000000","maintMargin":"0.00000000","initialMargin":"0.00000000","positionInitialMargin":"0.00000000","openOrderInitialMargin":"0.00000000","updateTime":1625474304765}],"positions":[{"symbol":"BTCUSDT","initialMargin":"0","maintMargin":"0","unrealizedProfit":"0.00000000","positionInitialMargin":"0","openOrderInitialMargin":"0","leverage":"100","entryPrice":"0.00000","maxNotional":"250000","bidNotional":"0","askNotional":"0","positionSide":"BOTH","positionAmt":"0","updateTime":0}]}"#;
+        let um: UmAccountInformationResponse = from_str(um).unwrap();
+        assert_eq!(
+            um.assets[0].cross_wallet_balance,
+            Decimal::new(2372469206, 8)
+        );
+        assert_eq!(um.positions[0].leverage, Decimal::new(100, 0));
+        assert_eq!(um.positions[0].max_notional, Decimal::new(250000, 0));
+
+        let cm = r#"{"assets":[{"asset":"BTC","crossWalletBalance":"0.00241969","crossUnPnl":"0.00000000","maintMargin":"0.00000000","initialMargin":"0.00000000","positionInitialMargin":"0.00000000","openOrderInitialMargin":"0.00000000","updateTime":1625474304765}],"positions":[{"symbol":"BTCUSD_201225","positionAmt":"0","initialMargin":"0","maintMargin":"0","unrealizedProfit":"0.00000000","positionInitialMargin":"0","openOrderInitialMargin":"0","leverage":"125","positionSide":"BOTH","entryPrice":"0.0","maxQty":"50","updateTime":0}]}"#;
+        let cm: CmAccountInformationResponse = from_str(cm).unwrap();
+        assert_eq!(cm.assets[0].asset, "BTC");
+        assert_eq!(cm.positions[0].symbol, "BTCUSD_201225");
+        assert_eq!(cm.positions[0].max_qty, Decimal::new(50, 0));
+    }
+
+    #[test]
+    fn position_risk() {
+        let um = r#"[{"entryPrice":"0.00000","leverage":"10","markPrice":"6679.50671178","maxNotionalValue":"20000000","positionAmt":"0.000","notional":"0","symbol":"BTCUSDT","unRealizedProfit":"0.00000000","liquidationPrice":"0","positionSide":"BOTH","updateTime":0}]"#;
+        let um: Vec<UmPositionRisk> = from_str(um).unwrap();
+        assert_eq!(um[0].mark_price, Decimal::new(667950671178, 8));
+        assert_eq!(um[0].max_notional_value, Decimal::new(20000000, 0));
+
+        let cm = r#"[{"symbol":"BTCUSD_201225","positionAmt":"1","entryPrice":"11707.70000003","markPrice":"11788.66626667","unRealizedProfit":"0.00005866","liquidationPrice":"6170.20509059","leverage":"125","positionSide":"LONG","updateTime":1627026881327,"maxQty":"50","notionalValue":"0.00084827"}]"#;
+        let cm: Vec<CmPositionRisk> = from_str(cm).unwrap();
+        assert_eq!(cm[0].position_side, "LONG");
+        assert_eq!(cm[0].unrealized_profit, Decimal::new(5866, 8));
+        assert_eq!(cm[0].notional_value, Decimal::new(84827, 8));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::{
+    define_request,
+    models::{
+        spot::NewOrderResponseType,
+        usdm::{PositionSide, WorkingType},
+        OrderType, Product, Side, TimeInForce,
+    },
+    parser::{string_or_decimal, string_or_decimal_opt},
+};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StrategyType {
+    Stop,
+    #[default]
+    StopMarket,
+    TakeProfit,
+    TakeProfitMarket,
+    TrailingStopMarket,
+}
+
+define_request! {
+    Name => NewUmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/um/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub position_side: Option<PositionSide>,
+        /// Only `LIMIT` and `MARKET`, use `NewUmConditionalOrderRequest` for the others.
+        pub r#type: OrderType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub reduce_only: Option<bool>,
+        pub price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+    };
+    Response => UmOrder;
+}
+
+define_request! {
+    Name => QueryUmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/um/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => UmOrder;
+}
+
+define_request! {
+    Name => CancelUmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::DELETE;
+    Endpoint => "/papi/v1/um/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => UmOrder;
+}
+
+define_request! {
+    Name => NewCmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/cm/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub position_side: Option<PositionSide>,
+        /// Only `LIMIT` and `MARKET`, use `NewCmConditionalOrderRequest` for the others.
+        pub r#type: OrderType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub reduce_only: Option<bool>,
+        pub price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+    };
+    Response => CmOrder;
+}
+
+define_request! {
+    Name => QueryCmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/cm/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => CmOrder;
+}
+
+define_request! {
+    Name => CancelCmOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::DELETE;
+    Endpoint => "/papi/v1/cm/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => CmOrder;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UmOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cum_quote: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub side: String,
+    pub position_side: String,
+    pub reduce_only: bool,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmOrder {
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cum_base: Decimal,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub side: String,
+    pub position_side: String,
+    pub reduce_only: bool,
+    pub update_time: u64,
+}
+
+define_request! {
+    Name => NewUmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/um/conditional/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub position_side: Option<PositionSide>,
+        pub strategy_type: StrategyType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub reduce_only: Option<bool>,
+        pub price: Option<Decimal>,
+        pub working_type: Option<WorkingType>,
+        pub price_protect: Option<bool>,
+        pub new_client_strategy_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub activation_price: Option<Decimal>,
+        pub callback_rate: Option<Decimal>,
+    };
+    Response => ConditionalOrder;
+}
+
+define_request! {
+    Name => QueryUmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/um/conditional/openOrder";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub strategy_id: Option<u64>,
+        pub new_client_strategy_id: Option<String>,
+    };
+    Response => ConditionalOrder;
+}
+
+define_request! {
+    Name => CancelUmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::DELETE;
+    Endpoint => "/papi/v1/um/conditional/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub strategy_id: Option<u64>,
+        pub new_client_strategy_id: Option<String>,
+    };
+    Response => ConditionalOrder;
+}
+
+define_request! {
+    Name => NewCmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::POST;
+    Endpoint => "/papi/v1/cm/conditional/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub position_side: Option<PositionSide>,
+        pub strategy_type: StrategyType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub reduce_only: Option<bool>,
+        pub price: Option<Decimal>,
+        pub working_type: Option<WorkingType>,
+        pub price_protect: Option<bool>,
+        pub new_client_strategy_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub activation_price: Option<Decimal>,
+        pub callback_rate: Option<Decimal>,
+    };
+    Response => ConditionalOrder;
+}
+
+define_request! {
+    Name => QueryCmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::GET;
+    Endpoint => "/papi/v1/cm/conditional/openOrder";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub strategy_id: Option<u64>,
+        pub new_client_strategy_id: Option<String>,
+    };
+    Response => ConditionalOrder;
+}
+
+define_request! {
+    Name => CancelCmConditionalOrder;
+    Product => Product::PortfolioMargin;
+    Method => Method::DELETE;
+    Endpoint => "/papi/v1/cm/conditional/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub strategy_id: Option<u64>,
+        pub new_client_strategy_id: Option<String>,
+    };
+    Response => ConditionalOrder;
+}
+
+/// A UM or CM conditional order, which turns into a regular order once triggered.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionalOrder {
+    pub symbol: String,
+    pub strategy_id: u64,
+    pub new_client_strategy_id: String,
+    pub strategy_status: String,
+    pub strategy_type: StrategyType,
+    #[serde(with = "string_or_decimal")]
+    pub orig_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    pub reduce_only: bool,
+    pub side: String,
+    pub position_side: String,
+    pub time_in_force: String,
+    /// Only set for `TRAILING_STOP_MARKET`.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub activate_price: Option<Decimal>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub price_rate: Option<Decimal>,
+    pub working_type: String,
+    pub price_protect: bool,
+    pub book_time: u64,
+    pub update_time: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CmOrder, ConditionalOrder, StrategyType, UmOrder};
+    use rust_decimal::Decimal;
+    use serde_json::from_str;
+
+    #[test]
+    fn orders() {
+        let um = r#"{"clientOrderId":"testOrder","cumQty":"0","cumQuote":"0","executedQty":"0","orderId":22542179,"avgPrice":"0.00000","origQty":"10","price":"0","reduceOnly":false,"side":"BUY","positionSide":"SHORT","status":"NEW","symbol":"BTCUSDT","timeInForce":"GTD","type":"LIMIT","selfTradePreventionMode":"NONE","goodTillDate":1693207680000,"updateTime":1566818724722,"priceMatch":"NONE"}"#;
+        let um: UmOrder = from_str(um).unwrap();
+        assert_eq!(um.order_id, 22542179);
+        assert_eq!(um.orig_qty, Decimal::new(10, 0));
+
+        let cm = r#"{"clientOrderId":"testOrder","cumQty":"0","cumBase":"0","executedQty":"0","orderId":22542179,"avgPrice":"0.0","origQty":"10","price":"0","reduceOnly":false,"side":"BUY","positionSide":"SHORT","status":"NEW","symbol":"BTCUSD_200925","pair":"BTCUSD","timeInForce":"GTC","type":"LIMIT","updateTime":1566818724722}"#;
+        let cm: CmOrder = from_str(cm).unwrap();
+        assert_eq!(cm.pair, "BTCUSD");
+        assert_eq!(cm.cum_base, Decimal::ZERO);
+    }
+
+    #[test]
+    fn conditional_orders() {
+        let trailing = r#"{"newClientStrategyId":"testOrder","strategyId":123445,"strategyStatus":"NEW","strategyType":"TRAILING_STOP_MARKET","origQty":"10","price":"0","reduceOnly":false,"side":"BUY","positionSide":"SHORT","stopPrice":"9300","symbol":"BTCUSDT","timeInForce":"GTD","activatePrice":"9020","priceRate":"0.3","bookTime":1566818724710,"updateTime":1566818724722,"workingType":"CONTRACT_PRICE","priceProtect":false,"selfTradePreventionMode":"NONE","goodTillDate":1693207680000,"priceMatch":"NONE"}"#;
+        let order: ConditionalOrder = from_str(trailing).unwrap();
+        assert!(matches!(
+            order.strategy_type,
+            StrategyType::TrailingStopMarket
+        ));
+        assert_eq!(order.activate_price, Some(Decimal::new(9020, 0)));
+        assert_eq!(order.price_rate, Some(Decimal::new(3, 1)));
+
+        let stop = r#"{"newClientStrategyId":"stop","strategyId":123446,"strategyStatus":"NEW","strategyType":"STOP","origQty":"1","price":"9000","reduceOnly":true,"side":"SELL","positionSide":"BOTH","stopPrice":"9100","symbol":"BTCUSD_PERP","timeInForce":"GTC","bookTime":1566818724710,"updateTime":1566818724722,"workingType":"MARK_PRICE","priceProtect":true}"#;
+        let order: ConditionalOrder = from_str(stop).unwrap();
+        assert!(matches!(order.strategy_type, StrategyType::Stop));
+        assert_eq!(order.stop_price, Decimal::new(9100, 0));
+        assert_eq!(order.activate_price, None);
+    }
+}
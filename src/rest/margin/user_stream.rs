@@ -11,6 +11,7 @@ define_request! {
     Endpoint => "/papi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => UserDataStream;
 }
@@ -22,6 +23,7 @@ define_request! {
     Endpoint => "/papi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {};
 }
@@ -33,6 +35,7 @@ define_request! {
     Endpoint => "/papi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {};
 }
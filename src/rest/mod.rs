@@ -1,30 +1,60 @@
+pub mod classic_margin;
 pub mod coinm;
+mod credentials;
 pub mod margin;
+pub mod options;
+mod rate_limit;
+mod retry;
+mod signer;
 pub mod spot;
+mod time_sync;
 pub mod usdm;
+pub mod wallet;
 
+use self::{
+    credentials::CredentialStore,
+    rate_limit::RateLimiter,
+    time_sync::{Sample, TimeSync, SAMPLES},
+};
+pub use self::{
+    credentials::{ApiKey, Credentials, EnvCredentials, FileCredentials, Secret},
+    rate_limit::{RateLimitScope, RateLimitUsage},
+    retry::{classify, ConfirmableOrder, ErrorClass, ExponentialBackoff, RetryEvent, RetryPolicy},
+    signer::{Ed25519Signer, HmacSigner, RsaSigner, Signer},
+};
 use crate::{
     config::Config,
-    error::BinanceError::{self, *},
-    models::Product,
+    error::{
+        BinanceError::{self, *},
+        BinanceErrorCode,
+    },
+    models::{Product, RateLimit},
     BinanceResponseError,
 };
 use chrono::Utc;
 use fehler::{throw, throws};
-use hex::encode as hexify;
-use hmac::{Hmac, Mac};
-use log::{debug, trace};
+use log::debug;
 #[cfg(feature = "zero-copy")]
 use owning_ref::OwningHandle;
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT},
-    Client, Method, Response,
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER, USER_AGENT},
+    Client, Method, Response, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::from_str;
-use sha2::Sha256;
 #[cfg(feature = "zero-copy")]
 use std::ops::Deref;
+use std::{sync::Arc, time::Duration};
+use tokio::time::sleep;
+use url::form_urlencoded::byte_serialize;
+
+/// The longest the throttle sleeps for a window to reset, enough for the per-second and
+/// per-minute limits. Hourly and daily limits are reported as `RateLimited` instead.
+const MAX_THROTTLE_WAIT: Duration = Duration::from_secs(60);
+
+/// How often and how patiently `place_order` looks up an order of unknown outcome.
+const CONFIRM_ATTEMPTS: u32 = 4;
+const CONFIRM_DELAY: Duration = Duration::from_millis(500);
 
 pub trait Request: Serialize {
     const PRODUCT: Product;
@@ -32,15 +62,30 @@ pub trait Request: Serialize {
     const METHOD: Method;
     const KEYED: bool = false; // SIGNED imples KEYED no matter KEYED is true or false
     const SIGNED: bool = false;
-    type Response: DeserializeOwned;
+    /// Whether the call counts against the ORDERS rate limits.
+    const IS_ORDER: bool = false;
+    /// Whether the weight counts against the per-account (UID) limits of `/sapi` rather than
+    /// the per-IP ones.
+    const UID_WEIGHT: bool = false;
+    type Response: DeserializeOwned + Send;
+
+    /// The request weight this call consumes, used for client-side throttling.
+    fn weight(&self) -> u64 {
+        1
+    }
 }
 
+type RetryHook = dyn Fn(&RetryEvent) + Send + Sync;
+
 #[derive(Clone, Default)]
 pub struct Binance {
-    key: Option<String>,
-    secret: Option<String>,
+    credentials: CredentialStore,
     client: Client,
-    config: Config,
+    pub(crate) config: Config,
+    time_sync: TimeSync,
+    rate_limiter: RateLimiter,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    retry_hook: Option<Arc<RetryHook>>,
 }
 
 impl Binance {
@@ -49,64 +94,342 @@ impl Binance {
     }
 
     pub fn with_key(api_key: &str) -> Self {
+        Self::with_api_key(ApiKey::new(api_key))
+    }
+
+    pub fn with_key_and_secret(api_key: &str, api_secret: &str) -> Self {
+        Self::with_api_key(ApiKey::with_secret(api_key, api_secret))
+    }
+
+    /// Sign requests with an Ed25519 or RSA key instead of an HMAC secret, e.g.
+    /// `Binance::with_key_and_signer(key, Ed25519Signer::from_pkcs8_pem(&pem)?)`.
+    pub fn with_key_and_signer<S>(api_key: &str, signer: S) -> Self
+    where
+        S: Signer + 'static,
+    {
+        Self::with_api_key(ApiKey::with_signer(api_key, signer))
+    }
+
+    fn with_api_key(key: ApiKey) -> Self {
         Binance {
-            client: Client::new(),
-            key: Some(api_key.into()),
-            secret: None,
-            config: Config::default(),
+            credentials: CredentialStore::with_key(key),
+            ..Default::default()
         }
     }
 
-    pub fn with_key_and_secret(api_key: &str, api_secret: &str) -> Self {
+    /// Take the API key from `credentials`, e.g. `EnvCredentials::default()` or an async closure
+    /// returning an `ApiKey`. It is loaded before the first keyed request.
+    pub fn with_credentials<C>(credentials: C) -> Self
+    where
+        C: Credentials + 'static,
+    {
         Binance {
-            client: Client::new(),
-            key: Some(api_key.into()),
-            secret: Some(api_secret.into()),
-            config: Config::default(),
+            credentials: CredentialStore::new(Arc::new(credentials)),
+            ..Default::default()
         }
     }
 
+    /// Replace the credentials of this client and all its clones, including the websocket API
+    /// clients connected with it.
+    pub fn set_credentials<C>(&self, credentials: C)
+    where
+        C: Credentials + 'static,
+    {
+        self.credentials.set(Arc::new(credentials));
+    }
+
+    /// Load the credentials again, e.g. after the key was rotated in the vault. Shared by all
+    /// clones of this client. A websocket API session authenticated with `session_logon` keeps
+    /// the previous key until it logs on again.
+    #[throws(BinanceError)]
+    pub async fn refresh_credentials(&self) {
+        self.credentials.refresh().await?;
+    }
+
     pub fn config(&mut self, config: Config) {
         self.config = config;
     }
 
+    /// Stamp signed requests with the server clock instead of the local one.
+    ///
+    /// The offset to each product's server time is measured lazily before the first signed
+    /// request and re-measured once it is older than `refresh_interval`. A request rejected
+    /// with -1021 (timestamp outside recvWindow) triggers a resync and is retried once.
+    pub fn enable_time_sync(&mut self, refresh_interval: Duration) {
+        self.time_sync.enable(refresh_interval);
+    }
+
+    pub fn disable_time_sync(&mut self) {
+        self.time_sync.disable();
+    }
+
+    /// The last measured offset in milliseconds between the server clock and the local clock.
+    pub fn time_offset(&self, product: Product) -> Option<i64> {
+        self.time_sync.offset(product)
+    }
+
+    /// Wait before sending a request whose weight would exceed a known limit, instead of
+    /// letting Binance answer with 429. Limits are only known after `set_rate_limits`.
+    /// Waits longer than a minute, i.e. for an hourly or daily window, fail with `RateLimited`.
+    pub fn enable_rate_limit_throttle(&mut self) {
+        self.rate_limiter.set_throttle(true);
+    }
+
+    pub fn disable_rate_limit_throttle(&mut self) {
+        self.rate_limiter.set_throttle(false);
+    }
+
+    /// Register the limits of `product`, usually the `rate_limits` of its `ExchangeInformation`.
+    pub fn set_rate_limits(&self, product: Product, limits: &[RateLimit]) {
+        self.rate_limiter
+            .set_limits(product, RateLimitScope::Ip, limits);
+    }
+
+    /// Register the per-account limits of `/sapi`, which no endpoint reports. Binance documents
+    /// 180000 UID weight per minute.
+    pub fn set_uid_rate_limits(&self, product: Product, limits: &[RateLimit]) {
+        self.rate_limiter
+            .set_limits(product, RateLimitScope::Uid, limits);
+    }
+
+    /// The current usage of every rate limit seen for `product`.
+    pub fn rate_limit_usage(&self, product: Product) -> Vec<RateLimitUsage> {
+        self.rate_limiter.usage(product)
+    }
+
+    /// Resend failed requests according to `policy`, e.g. `ExponentialBackoff::default()`.
+    ///
+    /// GET requests are retried on any failure the policy accepts, other requests only when the
+    /// failure proves they were not executed. Order placement is never retried by `request`.
+    pub fn set_retry_policy<P>(&mut self, policy: P)
+    where
+        P: RetryPolicy + 'static,
+    {
+        self.retry_policy = Some(Arc::new(policy));
+    }
+
+    pub fn clear_retry_policy(&mut self) {
+        self.retry_policy = None;
+    }
+
+    /// Call `hook` before each retry, e.g. for logging.
+    pub fn on_retry<F>(&mut self, hook: F)
+    where
+        F: Fn(&RetryEvent) + Send + Sync + 'static,
+    {
+        self.retry_hook = Some(Arc::new(hook));
+    }
+
+    /// Measure the clock offset to the server of `product` now and return it in milliseconds.
+    #[throws(BinanceError)]
+    pub async fn sync_time(&self, product: Product) -> i64 {
+        let mut samples = Vec::with_capacity(SAMPLES);
+        for _ in 0..SAMPLES {
+            let sent_at = Utc::now().timestamp_millis();
+            let server_time = match time_sync::clock_source(product) {
+                Product::UsdMFutures => self.send(&usdm::ServerTimeRequest {}).await?.server_time,
+                Product::CoinMFutures => self.send(&coinm::ServerTimeRequest {}).await?.server_time,
+                Product::EuropeanOptions => {
+                    self.send(&options::ServerTimeRequest {}).await?.server_time
+                }
+                _ => self.send(&spot::ServerTimeRequest {}).await?.server_time,
+            };
+            let received_at = Utc::now().timestamp_millis();
+            samples.push(Sample {
+                sent_at,
+                received_at,
+                server_time: server_time as i64,
+            });
+        }
+
+        let offset = self.time_sync.update(product, &samples).unwrap_or_default();
+        debug!("[REST] clock offset for {product:?}: {offset}ms");
+        offset
+    }
+
     #[throws(BinanceError)]
     pub async fn request<R>(&self, req: R) -> RestResponse<R::Response>
+    where
+        R: Request,
+    {
+        self.execute(&req).await?
+    }
+
+    /// Place an order, retrying it under the retry policy when it carries a `newClientOrderId`.
+    ///
+    /// When the outcome of a failed attempt is unknown, the order is looked up by its client
+    /// order id instead of being placed again: if it shows up it is returned, otherwise the
+    /// original error is, since Binance may still make the order visible later.
+    #[throws(BinanceError)]
+    pub async fn place_order<R>(&self, req: R) -> RestResponse<R::Response>
+    where
+        R: ConfirmableOrder,
+    {
+        let mut attempt = 0;
+        loop {
+            let error = match self.send_synced(&req).await {
+                Ok(resp) => break resp,
+                Err(e) => e,
+            };
+            attempt += 1;
+
+            let (policy, query) = match (&self.retry_policy, req.query()) {
+                (Some(policy), Some(query)) => (policy, query),
+                _ => throw!(error),
+            };
+            let delay = match policy.next_delay(attempt, &error) {
+                Some(delay) => delay,
+                None => throw!(error),
+            };
+            self.notify_retry(R::ENDPOINT, attempt, delay, &error);
+            sleep(delay).await;
+
+            if !matches!(
+                classify(&error),
+                ErrorClass::Transient | ErrorClass::RateLimited(_)
+            ) {
+                match self.confirm(&query).await {
+                    Ok(order) => break wrap(req.confirmed(&order)),
+                    Err(_) => throw!(error),
+                }
+            }
+        }
+    }
+
+    /// Look up an order whose placement had an unknown outcome. Binance may only list it after
+    /// a while, so `NoSuchOrder` is retried with backoff before giving up.
+    #[throws(BinanceError)]
+    async fn confirm<Q>(&self, query: &Q) -> RestResponse<Q::Response>
+    where
+        Q: Request,
+    {
+        let mut delay = CONFIRM_DELAY;
+        let mut attempt = 1;
+        loop {
+            match self.execute(query).await {
+                Ok(order) => break order,
+                Err(e)
+                    if e.code() == Some(BinanceErrorCode::NoSuchOrder)
+                        && attempt < CONFIRM_ATTEMPTS =>
+                {
+                    debug!("[REST] order not visible yet, looking it up again in {delay:?}");
+                    sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(e) => throw!(e),
+            }
+        }
+    }
+
+    #[throws(BinanceError)]
+    async fn execute<R>(&self, req: &R) -> RestResponse<R::Response>
+    where
+        R: Request,
+    {
+        let mut attempt = 0;
+        loop {
+            let error = match self.send_synced(req).await {
+                Ok(resp) => break resp,
+                Err(e) => e,
+            };
+            attempt += 1;
+
+            let policy = match &self.retry_policy {
+                Some(policy) if retry::may_resend::<R>(classify(&error)) => policy,
+                _ => throw!(error),
+            };
+            let delay = match policy.next_delay(attempt, &error) {
+                Some(delay) => delay,
+                None => throw!(error),
+            };
+            self.notify_retry(R::ENDPOINT, attempt, delay, &error);
+            sleep(delay).await;
+        }
+    }
+
+    fn notify_retry(
+        &self,
+        endpoint: &'static str,
+        attempt: u32,
+        delay: Duration,
+        error: &BinanceError,
+    ) {
+        debug!("[REST] retrying {endpoint} in {delay:?} after attempt {attempt}: {error}");
+        if let Some(hook) = &self.retry_hook {
+            hook(&RetryEvent {
+                endpoint,
+                attempt,
+                delay,
+                error,
+            });
+        }
+    }
+
+    #[throws(BinanceError)]
+    async fn send_synced<R>(&self, req: &R) -> RestResponse<R::Response>
+    where
+        R: Request,
+    {
+        if R::SIGNED {
+            self.sync_time_if_stale(R::PRODUCT).await?;
+        }
+
+        match self.send(req).await {
+            Err(e)
+                if e.code() == Some(BinanceErrorCode::InvalidTimestamp)
+                    && R::SIGNED
+                    && self.time_sync.is_enabled() =>
+            {
+                self.sync_time(R::PRODUCT).await?;
+                self.send(req).await?
+            }
+            resp => resp?,
+        }
+    }
+
+    #[throws(BinanceError)]
+    async fn send<R>(&self, req: &R) -> RestResponse<R::Response>
     where
         R: Request,
     {
         let mut params = if matches!(R::METHOD, Method::GET) {
-            serde_qs::to_string(&req)?
+            serde_qs::to_string(req)?
         } else {
             String::new()
         };
 
         let body = if !matches!(R::METHOD, Method::GET) {
-            serde_qs::to_string(&req)?
+            serde_qs::to_string(req)?
         } else {
             String::new()
         };
 
-        if R::SIGNED {
+        let credentials = if R::SIGNED || R::KEYED {
+            Some(self.credentials().await?)
+        } else {
+            None
+        };
+
+        // Stamp the request only after waiting, or the wait counts against recvWindow.
+        self.throttle(req).await?;
+
+        if let (true, Some(credentials)) = (R::SIGNED, &credentials) {
             if !params.is_empty() {
                 params.push('&');
             }
-            params.push_str(&format!("timestamp={}", Utc::now().timestamp_millis()));
+            params.push_str(&format!("timestamp={}", self.timestamp(R::PRODUCT)));
             params.push_str(&format!("&recvWindow={}", self.config.recv_window));
 
-            let signature = self.signature(&params, &body)?;
-            params.push_str(&format!("&signature={}", signature));
+            // Ed25519 and RSA signatures are base64 and have to be percent-encoded.
+            let signature = credentials.signature(&params, &body)?;
+            params.push_str("&signature=");
+            params.extend(byte_serialize(signature.as_bytes()));
         }
 
         let path = R::ENDPOINT.to_string();
 
-        let base = match R::PRODUCT {
-            Product::Spot => &self.config.rest_api_endpoint,
-            Product::UsdMFutures => &self.config.usdm_futures_rest_api_endpoint,
-            Product::CoinMFutures => &self.config.coinm_futures_rest_api_endpoint,
-            Product::EuropeanOptions => &self.config.european_options_rest_api_endpoint,
-            Product::PortfolioMargin => &self.config.portfolio_margin_rest_api_endpoint,
-        };
+        let base = self.config.rest_endpoint(R::PRODUCT);
         let url = format!("{base}{path}?{params}");
 
         let mut custom_headers = HeaderMap::new();
@@ -117,15 +440,10 @@ impl Binance {
                 HeaderValue::from_static("application/x-www-form-urlencoded"),
             );
         }
-        if R::SIGNED || R::KEYED {
-            let key = match &self.key {
-                Some(key) => key,
-                None => throw!(MissingApiKey),
-            };
-            custom_headers.insert(
-                HeaderName::from_static("x-mbx-apikey"),
-                HeaderValue::from_str(key)?,
-            );
+        if let Some(credentials) = &credentials {
+            let mut key = HeaderValue::from_str(credentials.api_key())?;
+            key.set_sensitive(true);
+            custom_headers.insert(HeaderName::from_static("x-mbx-apikey"), key);
         }
 
         debug!("[REST] url: {url}, body: {body}");
@@ -137,35 +455,85 @@ impl Binance {
             .body(body)
             .send()
             .await?;
+        self.rate_limiter.record(R::PRODUCT, resp.headers());
+
+        self.handle_response(R::ENDPOINT, resp).await?
+    }
 
-        self.handle_response(resp).await?
+    /// The current time in milliseconds on the server clock of `product`, as far as it is known.
+    pub(crate) fn timestamp(&self, product: Product) -> i64 {
+        Utc::now().timestamp_millis() + self.time_sync.offset(product).unwrap_or(0)
     }
 
+    /// Measure the clock offset of `product` if time sync is enabled and the last one is stale.
     #[throws(BinanceError)]
-    fn signature(&self, params: &str, body: &str) -> String {
-        let secret = match &self.secret {
-            Some(s) => s,
-            None => throw!(MissingApiSecret),
+    pub(crate) async fn sync_time_if_stale(&self, product: Product) {
+        if self.time_sync.needs_sync(product) {
+            self.sync_time(product).await?;
+        }
+    }
+
+    /// The current credentials, loaded on first use.
+    #[throws(BinanceError)]
+    pub(crate) async fn credentials(&self) -> Arc<ApiKey> {
+        self.credentials.get().await?
+    }
+
+    /// Sign with the credentials loaded last. Requests sign with the key they sent in the
+    /// header instead, which a concurrent refresh may already have replaced.
+    #[cfg(test)]
+    #[throws(BinanceError)]
+    pub(crate) fn signature(&self, params: &str, body: &str) -> String {
+        match self.credentials.current() {
+            Some(credentials) => credentials.signature(params, body)?,
+            None => throw!(MissingApiKey),
+        }
+    }
+
+    /// Wait until `req` fits into the known limits of its product.
+    #[throws(BinanceError)]
+    pub(crate) async fn throttle<R: Request>(&self, req: &R) {
+        if !self.rate_limiter.is_throttling() {
+            return;
+        }
+        let product = R::PRODUCT;
+        let scope = if R::UID_WEIGHT {
+            RateLimitScope::Uid
+        } else {
+            RateLimitScope::Ip
         };
-        // Signature: hex(HMAC_SHA256(queries + data))
-        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
-        let sign_message = format!("{}{}", params, body);
-        trace!("Sign message: {}", sign_message);
-        mac.update(sign_message.as_bytes());
-        let signature = hexify(mac.finalize().into_bytes());
-        signature
+        let orders = if R::IS_ORDER { 1 } else { 0 };
+        while let Some(wait) = self
+            .rate_limiter
+            .reserve(product, scope, req.weight(), orders)
+        {
+            if wait > MAX_THROTTLE_WAIT {
+                throw!(RateLimited(product, wait));
+            }
+            debug!("rate limit reached for {product:?}, waiting {wait:?}");
+            sleep(wait).await;
+        }
     }
 
     #[cfg(not(feature = "zero-copy"))]
     #[throws(BinanceError)]
-    async fn handle_response<O: DeserializeOwned>(&self, resp: Response) -> RestResponse<O> {
+    async fn handle_response<O: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        resp: Response,
+    ) -> RestResponse<O> {
         let status = resp.status();
+        let retry_after = retry_after(resp.headers());
         let body = resp.text().await?;
 
         if cfg!(feature = "print-response") {
             debug!("Response is {status} {body}");
         };
 
+        if !status.is_success() {
+            throw!(http_error(status, endpoint, retry_after, body));
+        }
+
         match from_str(&body) {
             Ok(v) => v,
             Err(e) => match from_str::<BinanceResponseError>(&body) {
@@ -177,19 +545,28 @@ impl Binance {
 
     #[cfg(feature = "zero-copy")]
     #[throws(BinanceError)]
-    async fn handle_response<O: DeserializeOwned>(&self, resp: Response) -> RestResponse<O> {
+    async fn handle_response<O: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        resp: Response,
+    ) -> RestResponse<O> {
         let status = resp.status();
+        let retry_after = retry_after(resp.headers());
         let body = resp.text().await?;
 
         if cfg!(feature = "print-response") {
             debug!("Response is {status} {body}");
         };
 
+        if !status.is_success() {
+            throw!(http_error(status, endpoint, retry_after, body));
+        }
+
         OwningHandle::try_new(body, |body| -> Result<_, BinanceError> {
             let body = unsafe { &*body };
             match from_str(body) {
                 Ok(v) => Ok(C(v)),
-                Err(e) => match from_str::<BinanceResponseError>(&body) {
+                Err(e) => match from_str::<BinanceResponseError>(body) {
                     Ok(e) => throw!(e),
                     Err(_) => throw!(e),
                 },
@@ -198,6 +575,51 @@ impl Binance {
     }
 }
 
+/// Binance sends `Retry-After` in seconds along with 429 and 418 responses.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+fn http_error(
+    status: StatusCode,
+    endpoint: &str,
+    retry_after: Option<Duration>,
+    body: String,
+) -> BinanceError {
+    match from_str::<BinanceResponseError>(&body) {
+        Ok(e) => ApiError {
+            status,
+            endpoint: endpoint.into(),
+            code: e.code.into(),
+            msg: e.msg,
+            retry_after,
+        },
+        Err(_) => HttpError {
+            status,
+            endpoint: endpoint.into(),
+            body,
+            retry_after,
+        },
+    }
+}
+
+#[cfg(not(feature = "zero-copy"))]
+pub(crate) fn wrap<O>(o: O) -> RestResponse<O> {
+    o
+}
+
+#[cfg(feature = "zero-copy")]
+pub(crate) fn wrap<O>(o: O) -> RestResponse<O> {
+    OwningHandle::new_with_fn(String::new(), move |_| C(o))
+}
+
 #[cfg(feature = "zero-copy")]
 #[derive(Clone, Copy, Debug)]
 #[repr(transparent)]
@@ -217,9 +639,12 @@ pub type RestResponse<O> = O;
 
 #[cfg(test)]
 mod test {
-    use super::Binance;
+    use super::{http_error, usdm, Binance, Request};
+    use crate::{BinanceError, BinanceErrorCode};
     use anyhow::Error;
     use fehler::throws;
+    use reqwest::StatusCode;
+    use std::time::Duration;
     use url::{form_urlencoded::Serializer, Url};
 
     #[throws(Error)]
@@ -352,4 +777,39 @@ mod test {
             "1ee5a75760b9496a2144a22116e02bc0b7fdcf828781fa87ca273540dfcf2cb0"
         );
     }
+
+    #[test]
+    fn request_weight() {
+        assert_eq!(usdm::FundingRateRequest::default().weight(), 10);
+        let req = usdm::FundingRateRequest {
+            symbol: Some("BTCUSDT".into()),
+            ..Default::default()
+        };
+        assert_eq!(req.weight(), 1);
+        assert!(usdm::NewOrderRequest::IS_ORDER);
+        assert!(!usdm::CancelOrderRequest::IS_ORDER);
+    }
+
+    #[test]
+    fn http_errors() {
+        let e = http_error(
+            StatusCode::BAD_REQUEST,
+            "/fapi/v1/order",
+            None,
+            r#"{"code":-1021,"msg":"Timestamp for this request is outside of the recvWindow."}"#
+                .into(),
+        );
+        assert_eq!(e.code(), Some(BinanceErrorCode::InvalidTimestamp));
+        assert_eq!(e.status(), Some(StatusCode::BAD_REQUEST));
+
+        let e = http_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "/api/v3/depth",
+            Some(Duration::from_secs(30)),
+            "".into(),
+        );
+        assert!(matches!(e, BinanceError::HttpError { .. }));
+        assert!(e.is_rate_limited());
+        assert_eq!(e.retry_after(), Some(Duration::from_secs(30)));
+    }
 }
@@ -0,0 +1,35 @@
+use crate::models::{
+    options::{Asset, Position, UnderlyingGreeks},
+    Product,
+};
+use reqwest::Method;
+
+crate::define_request! {
+    Name => AccountInformation;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/account";
+    Signed => true;
+    Weight => 3;
+    Request => {};
+    Response => {
+        pub asset: Vec<Asset>,
+        pub greek: Vec<UnderlyingGreeks>,
+        pub time: u64,
+        /// `NORMAL`, `ADD_MARGIN`, `REDUCE_ONLY` or `FORCE_LIQUIDATION`.
+        pub risk_level: Option<String>,
+    };
+}
+
+crate::define_request! {
+    Name => PositionInformation;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/position";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<Position>;
+}
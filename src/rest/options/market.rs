@@ -0,0 +1,254 @@
+use crate::models::{
+    options::OptionSide,
+    spot::{Asks, Bids},
+    Filter, Product, RateLimit, ServerTime,
+};
+use crate::parser::{string_or, string_or_decimal};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+crate::define_request! {
+    Name => Ping;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/ping";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
+
+crate::define_request! {
+    Name => ServerTime;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/time";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => ServerTime;
+}
+
+crate::define_request! {
+    Name => ExchangeInformation;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/exchangeInfo";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {
+        pub timezone: String,
+        pub server_time: u64,
+        pub option_contracts: Vec<OptionContract>,
+        pub option_assets: Vec<OptionAsset>,
+        pub option_symbols: Vec<Symbol>,
+        pub rate_limits: Vec<RateLimit>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionContract {
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub underlying: String,
+    pub settle_asset: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct OptionAsset {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub symbol: String,
+    pub underlying: String,
+    pub quote_asset: String,
+    pub side: OptionSide,
+    #[serde(with = "string_or_decimal")]
+    pub strike_price: Decimal,
+    pub expiry_date: u64,
+    pub unit: u64,
+    #[serde(with = "string_or_decimal")]
+    pub maker_fee_rate: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub taker_fee_rate: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub min_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maintenance_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub min_initial_margin: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub min_maintenance_margin: Decimal,
+    pub price_scale: u32,
+    pub quantity_scale: u32,
+    pub filters: Vec<Filter>,
+}
+
+crate::define_request! {
+    Name => OrderBook;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/depth";
+    Signed => false;
+    Weight(req) => match req.limit.unwrap_or(100) {
+        0..=100 => 2,
+        101..=500 => 5,
+        _ => 10,
+    };
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+    };
+    Response => {
+        #[serde(rename = "T")]
+        pub transaction_time: u64,
+        #[serde(rename = "u")]
+        pub update_id: u64,
+        pub bids: Vec<Bids>,
+        pub asks: Vec<Asks>,
+    };
+}
+
+crate::define_request! {
+    Name => MarkPrice;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/mark";
+    Signed => false;
+    Weight => 5;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<MarkPrice>;
+}
+
+/// The mark price, implied volatilities and greeks of an option.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPrice {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "bidIV", with = "string_or_decimal")]
+    pub bid_iv: Decimal,
+    #[serde(rename = "askIV", with = "string_or_decimal")]
+    pub ask_iv: Decimal,
+    #[serde(rename = "markIV", with = "string_or_decimal")]
+    pub mark_iv: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub delta: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub theta: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub gamma: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub vega: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub high_price_limit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub low_price_limit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub risk_free_interest: Decimal,
+}
+
+crate::define_request! {
+    Name => IndexPrice;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/index";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        /// e.g. `BTCUSDT`
+        pub underlying: String,
+    };
+    Response => {
+        pub time: u64,
+        #[serde(with = "string_or_decimal")]
+        pub index_price: Decimal,
+    };
+}
+
+crate::define_request! {
+    Name => Klines;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/klines";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Kline>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Kline {
+    pub open_time: u64,
+    pub close_time: u64,
+    pub interval: String,
+    #[serde(with = "string_or_decimal")]
+    pub open: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub high: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub low: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub close: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub volume: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub taker_volume: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub taker_amount: Decimal,
+    pub trade_count: u64,
+}
+
+crate::define_request! {
+    Name => OpenInterest;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/openInterest";
+    Signed => false;
+    Weight => 0;
+    Request => {
+        /// e.g. `ETH`
+        pub underlying_asset: String,
+        /// e.g. `221225`
+        pub expiration: String,
+    };
+    Response => Vec<OpenInterest>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub sum_open_interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub sum_open_interest_usd: Decimal,
+    #[serde(with = "string_or")]
+    pub timestamp: u64,
+}
@@ -0,0 +1,160 @@
+use crate::models::{
+    options::{BatchOrderResult, Order},
+    OrderType, Product, Side, TimeInForce,
+};
+use crate::parser::json_string;
+use crate::rest::ConfirmableOrder;
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NewOrderResponseType {
+    Ack,
+    Result,
+}
+
+crate::define_request! {
+    Name => NewOrder;
+    Product => Product::EuropeanOptions;
+    Method => Method::POST;
+    Endpoint => "/eapi/v1/order";
+    Signed => true;
+    Weight => 0;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        /// Only `LIMIT` is supported.
+        pub r#type: OrderType,
+        pub quantity: Decimal,
+        pub price: Option<Decimal>,
+        pub time_in_force: Option<TimeInForce>,
+        pub reduce_only: Option<bool>,
+        pub post_only: Option<bool>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub client_order_id: Option<String>,
+        pub is_mmp: Option<bool>,
+    };
+    Response => Order;
+}
+
+impl ConfirmableOrder for NewOrderRequest {
+    type Query = QueryOrderRequest;
+
+    fn query(&self) -> Option<QueryOrderRequest> {
+        Some(QueryOrderRequest {
+            symbol: self.symbol.clone(),
+            order_id: None,
+            client_order_id: Some(self.client_order_id.clone()?),
+        })
+    }
+
+    fn confirmed(&self, order: &Order) -> Order {
+        order.clone()
+    }
+}
+
+/// An order of a `PlaceMultipleOrdersRequest`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOrder {
+    pub symbol: String,
+    pub side: Side,
+    pub r#type: OrderType,
+    pub quantity: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_order_resp_type: Option<NewOrderResponseType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_mmp: Option<bool>,
+}
+
+crate::define_request! {
+    Name => PlaceMultipleOrders;
+    Product => Product::EuropeanOptions;
+    Method => Method::POST;
+    Endpoint => "/eapi/v1/batchOrders";
+    Signed => true;
+    Weight => 5;
+    IsOrder => true;
+    Request => {
+        /// At most 10 orders.
+        #[serde(with = "json_string")]
+        pub orders: Vec<BatchOrder>,
+    };
+    Response => Vec<BatchOrderResult>;
+}
+
+crate::define_request! {
+    Name => QueryOrder;
+    Product => Product::EuropeanOptions;
+    Method => Method::GET;
+    Endpoint => "/eapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelOrder;
+    Product => Product::EuropeanOptions;
+    Method => Method::DELETE;
+    Endpoint => "/eapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelMultipleOrders;
+    Product => Product::EuropeanOptions;
+    Method => Method::DELETE;
+    Endpoint => "/eapi/v1/batchOrders";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub order_ids: Vec<u64>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub client_order_ids: Vec<String>,
+    };
+    Response => Vec<BatchOrderResult>;
+}
+
+crate::define_request! {
+    Name => CancelAllOpenOrders;
+    Product => Product::EuropeanOptions;
+    Method => Method::DELETE;
+    Endpoint => "/eapi/v1/allOpenOrders";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
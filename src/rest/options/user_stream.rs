@@ -0,0 +1,40 @@
+use crate::rest::Product;
+use reqwest::Method;
+
+crate::define_request! {
+    Name => StartUserDataStream;
+    Product => Product::EuropeanOptions;
+    Method => Method::POST;
+    Endpoint => "/eapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {
+        pub listen_key: String,
+    };
+}
+
+crate::define_request! {
+    Name => KeepaliveUserDataStream;
+    Product => Product::EuropeanOptions;
+    Method => Method::PUT;
+    Endpoint => "/eapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
+
+crate::define_request! {
+    Name => CloseUserDataStream;
+    Product => Product::EuropeanOptions;
+    Method => Method::DELETE;
+    Endpoint => "/eapi/v1/listenKey";
+    Keyed => true;
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
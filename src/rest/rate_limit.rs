@@ -0,0 +1,357 @@
+use crate::models::{Interval, Product, RateLimit, RateLimitType};
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Who a limit is counted for. Binance counts per IP, except for some `/sapi` endpoints which
+/// count per account (UID) and report it in `X-SAPI-USED-UID-WEIGHT-*`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RateLimitScope {
+    Ip,
+    Uid,
+}
+
+/// The usage of one rate limit as last reported by Binance (or reserved locally).
+#[derive(Clone, Debug)]
+pub struct RateLimitUsage {
+    pub scope: RateLimitScope,
+    pub rate_limit_type: RateLimitType,
+    pub interval: Interval,
+    pub interval_num: u64,
+    pub used: u64,
+    /// Known only after `Binance::set_rate_limits` has been called for the product.
+    pub limit: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    product: Product,
+    scope: RateLimitScope,
+    rate_limit_type: RateLimitType,
+    interval: Interval,
+    interval_num: u64,
+}
+
+impl Key {
+    fn window_millis(&self) -> i64 {
+        self.interval.millis() * self.interval_num.max(1) as i64
+    }
+
+    fn window(&self, now: i64) -> i64 {
+        now / self.window_millis()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counter {
+    window: i64,
+    used: u64,
+    limit: Option<u64>,
+}
+
+impl Counter {
+    fn used_in(&self, window: i64) -> u64 {
+        if self.window == window {
+            self.used
+        } else {
+            0
+        }
+    }
+}
+
+/// Tracks request weight and order count per product and interval from the
+/// `X-MBX-USED-WEIGHT-*`, `X-MBX-ORDER-COUNT-*` and `X-SAPI-USED-{IP,UID}-WEIGHT-*` response
+/// headers.
+///
+/// Binance counts in fixed windows aligned to the epoch, so a counter is only
+/// meaningful while its window is still the current one.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RateLimiter {
+    throttle: bool,
+    counters: Arc<Mutex<HashMap<Key, Counter>>>,
+}
+
+impl RateLimiter {
+    pub fn set_throttle(&mut self, throttle: bool) {
+        self.throttle = throttle;
+    }
+
+    pub fn is_throttling(&self) -> bool {
+        self.throttle
+    }
+
+    pub fn set_limits(&self, product: Product, scope: RateLimitScope, limits: &[RateLimit]) {
+        let mut counters = self.counters.lock().unwrap();
+        for limit in limits {
+            let key = Key {
+                product,
+                scope,
+                rate_limit_type: limit.rate_limit_type,
+                interval: limit.interval,
+                interval_num: limit.interval_num,
+            };
+            counters.entry(key).or_default().limit = Some(limit.limit);
+        }
+    }
+
+    pub fn record(&self, product: Product, headers: &HeaderMap) {
+        let now = Utc::now().timestamp_millis();
+        let mut counters = self.counters.lock().unwrap();
+
+        for (name, value) in headers {
+            let (scope, rate_limit_type, interval, interval_num) =
+                match parse_header_name(name.as_str()) {
+                    Some(v) => v,
+                    None => continue,
+                };
+            let used: u64 = match value.to_str().ok().and_then(|v| v.parse().ok()) {
+                Some(used) => used,
+                None => continue,
+            };
+
+            let key = Key {
+                product,
+                scope,
+                rate_limit_type,
+                interval,
+                interval_num,
+            };
+            let window = key.window(now);
+            let counter = counters.entry(key).or_default();
+            if counter.window == window {
+                // Requests reserved locally may still be in flight, keep the larger count.
+                counter.used = counter.used.max(used);
+            } else {
+                counter.window = window;
+                counter.used = used;
+            }
+        }
+    }
+
+    pub fn usage(&self, product: Product) -> Vec<RateLimitUsage> {
+        let now = Utc::now().timestamp_millis();
+        let counters = self.counters.lock().unwrap();
+
+        counters
+            .iter()
+            .filter(|(key, _)| key.product == product)
+            .map(|(key, counter)| RateLimitUsage {
+                scope: key.scope,
+                rate_limit_type: key.rate_limit_type,
+                interval: key.interval,
+                interval_num: key.interval_num,
+                used: counter.used_in(key.window(now)),
+                limit: counter.limit,
+            })
+            .collect()
+    }
+
+    /// Reserve `weight` and `orders` against every known limit of `product` in `scope`. When one
+    /// of them would be exceeded nothing is reserved and the time until its window resets is
+    /// returned.
+    pub fn reserve(
+        &self,
+        product: Product,
+        scope: RateLimitScope,
+        weight: u64,
+        orders: u64,
+    ) -> Option<Duration> {
+        let now = Utc::now().timestamp_millis();
+        let mut counters = self.counters.lock().unwrap();
+
+        let mut wait = 0;
+        for (key, counter) in counters.iter() {
+            let limit = match counter.limit {
+                Some(limit) if key.product == product && key.scope == scope => limit,
+                _ => continue,
+            };
+            let cost = cost(key.rate_limit_type, weight, orders);
+            // A single request above the limit can never fit, let Binance reject it.
+            if cost == 0 || cost > limit {
+                continue;
+            }
+
+            let window = key.window(now);
+            if counter.used_in(window) + cost > limit {
+                wait = wait.max((window + 1) * key.window_millis() - now);
+            }
+        }
+        if wait > 0 {
+            return Some(Duration::from_millis(wait as u64));
+        }
+
+        for (key, counter) in counters.iter_mut() {
+            if key.product != product || key.scope != scope {
+                continue;
+            }
+            let window = key.window(now);
+            counter.used = counter.used_in(window) + cost(key.rate_limit_type, weight, orders);
+            counter.window = window;
+        }
+        None
+    }
+}
+
+fn cost(rate_limit_type: RateLimitType, weight: u64, orders: u64) -> u64 {
+    match rate_limit_type {
+        RateLimitType::RequestWeight => weight,
+        RateLimitType::Orders => orders,
+        RateLimitType::RawRequests => 1,
+    }
+}
+
+/// Parse headers like `x-mbx-used-weight-1m`, `x-mbx-order-count-10s` or
+/// `x-sapi-used-uid-weight-1m`.
+fn parse_header_name(name: &str) -> Option<(RateLimitScope, RateLimitType, Interval, u64)> {
+    let name = name.to_ascii_lowercase();
+    let (scope, rate_limit_type, suffix) =
+        if let Some(suffix) = name.strip_prefix("x-mbx-used-weight-") {
+            (RateLimitScope::Ip, RateLimitType::RequestWeight, suffix)
+        } else if let Some(suffix) = name.strip_prefix("x-mbx-order-count-") {
+            (RateLimitScope::Ip, RateLimitType::Orders, suffix)
+        } else if let Some(suffix) = name.strip_prefix("x-sapi-used-ip-weight-") {
+            (RateLimitScope::Ip, RateLimitType::RequestWeight, suffix)
+        } else if let Some(suffix) = name.strip_prefix("x-sapi-used-uid-weight-") {
+            (RateLimitScope::Uid, RateLimitType::RequestWeight, suffix)
+        } else {
+            return None;
+        };
+
+    let (num, unit) = suffix.split_at(suffix.len().checked_sub(1)?);
+    let interval = match unit {
+        "s" => Interval::Second,
+        "m" => Interval::Minute,
+        "h" => Interval::Hour,
+        "d" => Interval::Day,
+        _ => return None,
+    };
+    Some((scope, rate_limit_type, interval, num.parse().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_header_name, RateLimitScope, RateLimiter};
+    use crate::models::{Interval, Product, RateLimit, RateLimitType};
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn header_names() {
+        assert_eq!(
+            parse_header_name("X-MBX-USED-WEIGHT-1M"),
+            Some((
+                RateLimitScope::Ip,
+                RateLimitType::RequestWeight,
+                Interval::Minute,
+                1
+            ))
+        );
+        assert_eq!(
+            parse_header_name("x-mbx-order-count-10s"),
+            Some((
+                RateLimitScope::Ip,
+                RateLimitType::Orders,
+                Interval::Second,
+                10
+            ))
+        );
+        assert_eq!(
+            parse_header_name("X-SAPI-USED-IP-WEIGHT-1M"),
+            Some((
+                RateLimitScope::Ip,
+                RateLimitType::RequestWeight,
+                Interval::Minute,
+                1
+            ))
+        );
+        assert_eq!(
+            parse_header_name("X-SAPI-USED-UID-WEIGHT-1M"),
+            Some((
+                RateLimitScope::Uid,
+                RateLimitType::RequestWeight,
+                Interval::Minute,
+                1
+            ))
+        );
+        assert_eq!(parse_header_name("x-mbx-used-weight"), None);
+        assert_eq!(parse_header_name("x-mbx-uuid"), None);
+    }
+
+    #[test]
+    fn reserve_respects_reported_usage() {
+        let limiter = RateLimiter::default();
+        limiter.set_limits(
+            Product::Spot,
+            RateLimitScope::Ip,
+            &[RateLimit {
+                rate_limit_type: RateLimitType::RequestWeight,
+                interval: Interval::Day,
+                interval_num: 1,
+                limit: 100,
+            }],
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mbx-used-weight-1d", HeaderValue::from_static("95"));
+        limiter.record(Product::Spot, &headers);
+
+        assert!(limiter
+            .reserve(Product::Spot, RateLimitScope::Ip, 5, 0)
+            .is_none());
+        assert!(limiter
+            .reserve(Product::Spot, RateLimitScope::Ip, 1, 0)
+            .is_some());
+        assert!(limiter
+            .reserve(Product::Spot, RateLimitScope::Uid, 1, 0)
+            .is_none());
+        assert!(limiter
+            .reserve(Product::UsdMFutures, RateLimitScope::Ip, 1000, 0)
+            .is_none());
+
+        let usage = limiter.usage(Product::Spot);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].used, 100);
+        assert_eq!(usage[0].limit, Some(100));
+    }
+
+    #[test]
+    fn sapi_scopes() {
+        let limit = |limit| RateLimit {
+            rate_limit_type: RateLimitType::RequestWeight,
+            interval: Interval::Minute,
+            interval_num: 1,
+            limit,
+        };
+        let limiter = RateLimiter::default();
+        limiter.set_limits(Product::Sapi, RateLimitScope::Ip, &[limit(12000)]);
+        limiter.set_limits(Product::Sapi, RateLimitScope::Uid, &[limit(180000)]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-sapi-used-ip-weight-1m",
+            HeaderValue::from_static("11990"),
+        );
+        headers.insert(
+            "x-sapi-used-uid-weight-1m",
+            HeaderValue::from_static("18000"),
+        );
+        limiter.record(Product::Sapi, &headers);
+
+        assert!(limiter
+            .reserve(Product::Sapi, RateLimitScope::Ip, 900, 0)
+            .is_some());
+        assert!(limiter
+            .reserve(Product::Sapi, RateLimitScope::Uid, 900, 0)
+            .is_none());
+
+        let usage = limiter.usage(Product::Sapi);
+        let uid = usage
+            .iter()
+            .find(|u| u.scope == RateLimitScope::Uid)
+            .unwrap();
+        assert_eq!(uid.used, 18900);
+    }
+}
@@ -0,0 +1,192 @@
+use super::Request;
+use crate::error::{BinanceError, BinanceErrorCode};
+use reqwest::StatusCode;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Decides whether and when a failed REST request is sent again.
+pub trait RetryPolicy: Send + Sync {
+    /// The delay before the next attempt, or `None` to give up. `attempt` is the
+    /// number of attempts that failed so far, starting at 1.
+    fn next_delay(&self, attempt: u32, error: &BinanceError) -> Option<Duration>;
+}
+
+/// What a failure tells us about the request that caused it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// The request was not executed and can be sent again.
+    Transient,
+    /// The request may or may not have been executed.
+    Unknown,
+    /// Binance rejected the request because a rate limit was hit.
+    RateLimited(Option<Duration>),
+    /// Sending the request again will not help.
+    Fatal,
+}
+
+pub fn classify(error: &BinanceError) -> ErrorClass {
+    use BinanceErrorCode::*;
+
+    if let BinanceError::Reqwest(e) = error {
+        return if e.is_connect() {
+            ErrorClass::Transient
+        } else if e.is_timeout() || e.is_request() {
+            ErrorClass::Unknown
+        } else {
+            ErrorClass::Fatal
+        };
+    }
+
+    match error.status() {
+        // Retrying while banned only extends the ban.
+        Some(StatusCode::IM_A_TEAPOT) => return ErrorClass::Fatal,
+        Some(StatusCode::TOO_MANY_REQUESTS) => return ErrorClass::RateLimited(error.retry_after()),
+        _ => {}
+    }
+
+    match error.code() {
+        Some(Disconnected | ServerBusy | ServerOverloaded) => ErrorClass::Transient,
+        Some(UnexpectedResponse | Timeout) => ErrorClass::Unknown,
+        Some(TooManyRequests | TooManyOrders) => ErrorClass::RateLimited(error.retry_after()),
+        Some(_) => ErrorClass::Fatal,
+        // 5xx without an error code: the gateway gave up, execution status unknown.
+        None if error.is_server_error() => ErrorClass::Unknown,
+        None => ErrorClass::Fatal,
+    }
+}
+
+/// Whether a failed `R` may be resent by `Binance::request`.
+///
+/// GET requests are always safe to resend. Other requests are only resent when the
+/// failure proves they were not executed, and order placement never is; use
+/// `Binance::place_order` for that.
+pub(crate) fn may_resend<R: Request>(class: ErrorClass) -> bool {
+    if R::IS_ORDER {
+        return false;
+    }
+    R::METHOD == reqwest::Method::GET
+        || matches!(class, ErrorClass::Transient | ErrorClass::RateLimited(_))
+}
+
+/// Exponential backoff with jitter. Rate limited requests wait for `Retry-After` instead when it is given.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Randomize each delay between half and all of its value, so clients don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn next_delay(&self, attempt: u32, error: &BinanceError) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let delay = match classify(error) {
+            ErrorClass::Fatal => return None,
+            ErrorClass::RateLimited(Some(retry_after)) => return Some(retry_after),
+            _ => self
+                .base_delay
+                .saturating_mul(1 << (attempt - 1).min(16))
+                .min(self.max_delay),
+        };
+
+        if self.jitter {
+            let random = RandomState::new().build_hasher().finish();
+            let half = delay / 2;
+            Some(half + half.mul_f64((random % 1000) as f64 / 1000.))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+/// Passed to the hook registered with `Binance::on_retry` before each retry.
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    pub endpoint: &'static str,
+    pub attempt: u32,
+    pub delay: Duration,
+    pub error: &'a BinanceError,
+}
+
+/// An order placement that can be looked up by its client order id when its outcome is unknown.
+pub trait ConfirmableOrder: Request {
+    type Query: Request;
+
+    /// The request that queries this order, `None` when no `newClientOrderId` is set.
+    fn query(&self) -> Option<Self::Query>;
+
+    /// Build the placement response from the queried order.
+    fn confirmed(&self, order: &<Self::Query as Request>::Response) -> Self::Response;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{classify, ErrorClass, ExponentialBackoff, RetryPolicy};
+    use crate::{error::BinanceErrorCode, BinanceError};
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    fn api_error(status: StatusCode, code: i64) -> BinanceError {
+        BinanceError::ApiError {
+            status,
+            endpoint: "/fapi/v1/order".into(),
+            code: BinanceErrorCode::from(code),
+            msg: String::new(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn classification() {
+        assert_eq!(
+            classify(&api_error(StatusCode::SERVICE_UNAVAILABLE, -1001)),
+            ErrorClass::Transient
+        );
+        assert_eq!(
+            classify(&api_error(StatusCode::SERVICE_UNAVAILABLE, -1007)),
+            ErrorClass::Unknown
+        );
+        assert_eq!(
+            classify(&api_error(StatusCode::BAD_REQUEST, -1102)),
+            ErrorClass::Fatal
+        );
+        assert_eq!(
+            classify(&api_error(StatusCode::IM_A_TEAPOT, -1003)),
+            ErrorClass::Fatal
+        );
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = ExponentialBackoff {
+            jitter: false,
+            ..Default::default()
+        };
+        let e = api_error(StatusCode::SERVICE_UNAVAILABLE, -1001);
+        assert_eq!(policy.next_delay(1, &e), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(2, &e), Some(Duration::from_millis(400)));
+        assert_eq!(policy.next_delay(3, &e), None);
+        assert_eq!(
+            policy.next_delay(1, &api_error(StatusCode::BAD_REQUEST, -1102)),
+            None
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use crate::models::{
-    spot::{AccountInformation, OrderInfo},
-    OrderType, Product, Side, TimeInForce,
+    spot::{AccountInformation, PreventedMatch, TradeHistory},
+    Product,
 };
+use crate::parser::string_or_decimal;
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
 use reqwest::Method;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 crate::define_request! {
     Name => GetAccount;
@@ -11,25 +15,85 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/api/v3/account";
     Signed => true;
+    Weight => 20;
     Request => {};
     Response => AccountInformation;
 }
 
 crate::define_request! {
-    Name => Order;
+    Name => MyTrades;
     Product => Product::Spot;
     Method => Method::GET;
-    Endpoint => "/api/v3/order";
+    Endpoint => "/api/v3/myTrades";
     Signed => true;
+    Weight(req) => if req.order_id.is_some() { 5 } else { 20 };
     Request => {
         pub symbol: String,
-        pub qty: Decimal,
-        pub price: Option<Decimal>,
-        pub stop_price: Option<Decimal>,
-        pub order_side: Side,
-        pub order_type: OrderType,
-        pub time_in_force: TimeInForce,
-        pub new_client_order_id: Option<String>,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub from_id: Option<u64>,
+        pub limit: Option<u64>,
     };
-    Response => OrderInfo;
+    Response => Vec<TradeHistory>;
+}
+
+crate::define_request! {
+    Name => PreventedMatches;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/myPreventedMatches";
+    Signed => true;
+    Weight(req) => if req.order_id.is_some() || req.from_prevented_match_id.is_some() { 20 } else { 2 };
+    Request => {
+        pub symbol: String,
+        pub prevented_match_id: Option<u64>,
+        pub order_id: Option<u64>,
+        pub from_prevented_match_id: Option<u64>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<PreventedMatch>;
+}
+
+crate::define_request! {
+    Name => CommissionRates;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/account/commission";
+    Signed => true;
+    Weight => 20;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub symbol: String,
+        pub standard_commission: CommissionRates,
+        pub tax_commission: CommissionRates,
+        pub discount: CommissionDiscount,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CommissionRates {
+    #[serde(with = "string_or_decimal")]
+    pub maker: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub taker: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub buyer: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub seller: Decimal,
+}
+
+/// The commission discount for paying fees in `discount_asset`, usually BNB.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub discount: Decimal,
 }
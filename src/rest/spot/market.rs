@@ -1,6 +1,16 @@
-use crate::models::Product;
-
+use crate::models::{
+    spot::{
+        AggTrade, BookTickers, ExchangeFilter, KlineSummary, MarketTrade, OrderBook,
+        PriceStatistics, Prices, SelfTradePreventionMode, WindowStatistics,
+    },
+    Filter, OrderType, Product, RateLimit, ServerTime,
+};
+use crate::parser::{json_string, string_or_decimal};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
 use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 crate::define_request! {
     Name => Ping;
@@ -8,6 +18,310 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/api/v3/ping";
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {};
 }
+
+crate::define_request! {
+    Name => ServerTime;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/time";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => ServerTime;
+}
+
+crate::define_request! {
+    Name => OrderBook;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/depth";
+    Signed => false;
+    Weight(req) => match req.limit.unwrap_or(100) {
+        0..=100 => 5,
+        101..=500 => 25,
+        501..=1000 => 50,
+        _ => 250,
+    };
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+    };
+    Response => OrderBook;
+}
+
+crate::define_request! {
+    Name => ExchangeInformation;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/exchangeInfo";
+    Signed => false;
+    Weight => 20;
+    Request => {
+        /// Only one of `symbol`, `symbols` and `permissions` may be set.
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+        /// e.g. `SPOT`, `MARGIN` or `TRD_GRP_004`.
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub permissions: Vec<String>,
+        pub show_permission_sets: Option<bool>,
+        /// `TRADING`, `HALT` or `BREAK`.
+        pub symbol_status: Option<String>,
+    };
+    Response => {
+        pub timezone: String,
+        pub server_time: u64,
+        pub rate_limits: Vec<RateLimit>,
+        pub exchange_filters: Vec<ExchangeFilter>,
+        pub symbols: Vec<Symbol>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub symbol: String,
+    pub status: String,
+    pub base_asset: String,
+    pub base_asset_precision: u32,
+    pub quote_asset: String,
+    pub quote_asset_precision: u32,
+    pub base_commission_precision: u32,
+    pub quote_commission_precision: u32,
+    pub order_types: Vec<OrderType>,
+    pub iceberg_allowed: bool,
+    pub oco_allowed: bool,
+    #[serde(default)]
+    pub oto_allowed: bool,
+    pub quote_order_qty_market_allowed: bool,
+    pub allow_trailing_stop: bool,
+    pub cancel_replace_allowed: bool,
+    pub is_spot_trading_allowed: bool,
+    pub is_margin_trading_allowed: bool,
+    pub filters: Vec<Filter>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// The symbol is tradable by accounts having all the permissions of any one set.
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<String>>,
+    pub default_self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    #[serde(default)]
+    pub allowed_self_trade_prevention_modes: Vec<SelfTradePreventionMode>,
+}
+
+crate::define_request! {
+    Name => RecentTrades;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/trades";
+    Signed => false;
+    Weight => 25;
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<MarketTrade>;
+}
+
+crate::define_request! {
+    Name => HistoricalTrades;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/historicalTrades";
+    Keyed => true;
+    Signed => false;
+    Weight => 25;
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+        /// The trade id to fetch from, the most recent trades by default.
+        pub from_id: Option<u64>,
+    };
+    Response => Vec<MarketTrade>;
+}
+
+crate::define_request! {
+    Name => AggTrades;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/aggTrades";
+    Signed => false;
+    Weight => 4;
+    Request => {
+        pub symbol: String,
+        pub from_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<AggTrade>;
+}
+
+crate::define_request! {
+    Name => Klines;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/klines";
+    Signed => false;
+    Weight => 2;
+    Request => {
+        pub symbol: String,
+        /// e.g. `1s`, `1m`, `1h`, `1d`, `1w` or `1M`.
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub time_zone: Option<String>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => UiKlines;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/uiKlines";
+    Signed => false;
+    Weight => 2;
+    Request => {
+        pub symbol: String,
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub time_zone: Option<String>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => AvgPrice;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/avgPrice";
+    Signed => false;
+    Weight => 2;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        /// The length of the averaged window in minutes.
+        pub mins: u64,
+        #[serde(with = "string_or_decimal")]
+        pub price: Decimal,
+        pub close_time: u64,
+    };
+}
+
+/// The weight of the 24hr and price tickers, which depends on how many symbols are asked.
+fn ticker_weight(symbol: &Option<String>, symbols: &[String], single: u64, all: u64) -> u64 {
+    if symbol.is_some() {
+        single
+    } else if symbols.is_empty() {
+        all
+    } else {
+        match symbols.len() {
+            0..=20 => single,
+            21..=100 => all / 2,
+            _ => all,
+        }
+    }
+}
+
+/// The weight of the rolling window and trading day tickers, 4 per symbol up to 200.
+fn window_weight(symbol: &Option<String>, symbols: &[String]) -> u64 {
+    let count = if symbol.is_some() { 1 } else { symbols.len() };
+    (count as u64 * 4).min(200)
+}
+
+crate::define_request! {
+    Name => Ticker24hr;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/ticker/24hr";
+    Signed => false;
+    Weight(req) => ticker_weight(&req.symbol, &req.symbols, 2, 80);
+    Request => {
+        /// Every symbol is returned when neither `symbol` nor `symbols` is set.
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+    };
+    Response => PriceStatistics;
+}
+
+crate::define_request! {
+    Name => RollingWindowTicker;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/ticker";
+    Signed => false;
+    Weight(req) => window_weight(&req.symbol, &req.symbols);
+    Request => {
+        /// Either `symbol` or `symbols` must be set.
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+        /// e.g. `1m`, `2h` or `7d`, `1d` by default.
+        pub window_size: Option<String>,
+    };
+    Response => WindowStatistics;
+}
+
+crate::define_request! {
+    Name => TradingDayTicker;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/ticker/tradingDay";
+    Signed => false;
+    Weight(req) => window_weight(&req.symbol, &req.symbols);
+    Request => {
+        /// Either `symbol` or `symbols` must be set.
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+        /// e.g. `-1:00` or `05:45`, UTC by default.
+        pub time_zone: Option<String>,
+    };
+    Response => WindowStatistics;
+}
+
+crate::define_request! {
+    Name => PriceTicker;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/ticker/price";
+    Signed => false;
+    Weight(req) => if req.symbol.is_some() { 2 } else { 4 };
+    Request => {
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+    };
+    Response => Prices;
+}
+
+crate::define_request! {
+    Name => BookTicker;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/ticker/bookTicker";
+    Signed => false;
+    Weight(req) => if req.symbol.is_some() { 2 } else { 4 };
+    Request => {
+        pub symbol: Option<String>,
+        #[serde(with = "json_string", skip_serializing_if = "Vec::is_empty")]
+        pub symbols: Vec<String>,
+    };
+    Response => BookTickers;
+}
@@ -1,7 +1,9 @@
 mod account;
 mod market;
+mod trade;
 mod user_stream;
 
 pub use account::*;
 pub use market::*;
+pub use trade::*;
 pub use user_stream::*;
@@ -0,0 +1,324 @@
+use crate::models::{
+    spot::{
+        CancelReplaceMode, CancelRestrictions, NewOrderResponseType, Order, OrderList,
+        OrderResponse, SelfTradePreventionMode,
+    },
+    OrderType, Product, Side, TimeInForce,
+};
+use crate::parser::string_or_decimal;
+use crate::rest::ConfirmableOrder;
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+crate::define_request! {
+    Name => NewOrder;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub r#type: OrderType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub quote_order_qty: Option<Decimal>,
+        pub price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub trailing_delta: Option<u64>,
+        pub iceberg_qty: Option<Decimal>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    };
+    Response => OrderResponse;
+}
+
+impl ConfirmableOrder for NewOrderRequest {
+    type Query = QueryOrderRequest;
+
+    fn query(&self) -> Option<QueryOrderRequest> {
+        Some(QueryOrderRequest {
+            symbol: self.symbol.clone(),
+            order_id: None,
+            orig_client_order_id: Some(self.new_client_order_id.clone()?),
+        })
+    }
+
+    fn confirmed(&self, order: &Order) -> OrderResponse {
+        OrderResponse::Result(Box::new(order.into()))
+    }
+}
+
+crate::define_request! {
+    Name => TestNewOrder;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/order/test";
+    Signed => true;
+    Weight(req) => if req.compute_commission_rates == Some(true) { 20 } else { 1 };
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub r#type: OrderType,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub quote_order_qty: Option<Decimal>,
+        pub price: Option<Decimal>,
+        pub new_client_order_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub trailing_delta: Option<u64>,
+        pub iceberg_qty: Option<Decimal>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        pub compute_commission_rates: Option<bool>,
+    };
+    Response => {
+        /// Only set when `compute_commission_rates` is.
+        pub standard_commission_for_order: Option<OrderCommission>,
+        pub tax_commission_for_order: Option<OrderCommission>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct OrderCommission {
+    #[serde(with = "string_or_decimal")]
+    pub maker: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub taker: Decimal,
+}
+
+crate::define_request! {
+    Name => QueryOrder;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/order";
+    Signed => true;
+    Weight => 4;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
+crate::define_request! {
+    Name => CancelOrder;
+    Product => Product::Spot;
+    Method => Method::DELETE;
+    Endpoint => "/api/v3/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+        pub new_client_order_id: Option<String>,
+        pub cancel_restrictions: Option<CancelRestrictions>,
+    };
+    Response => {
+        pub symbol: String,
+        pub orig_client_order_id: String,
+        pub order_id: u64,
+        pub order_list_id: i64,
+        pub client_order_id: String,
+        pub transact_time: u64,
+        #[serde(with = "string_or_decimal")]
+        pub price: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub orig_qty: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub executed_qty: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub cummulative_quote_qty: Decimal,
+        pub status: String,
+        pub time_in_force: String,
+        #[serde(rename = "type")]
+        pub type_name: String,
+        pub side: String,
+    };
+}
+
+crate::define_request! {
+    Name => CancelReplaceOrder;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/order/cancelReplace";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub side: Side,
+        pub r#type: OrderType,
+        pub cancel_replace_mode: CancelReplaceMode,
+        pub time_in_force: Option<TimeInForce>,
+        pub quantity: Option<Decimal>,
+        pub quote_order_qty: Option<Decimal>,
+        pub price: Option<Decimal>,
+        pub cancel_new_client_order_id: Option<String>,
+        pub cancel_orig_client_order_id: Option<String>,
+        pub cancel_order_id: Option<u64>,
+        pub new_client_order_id: Option<String>,
+        pub stop_price: Option<Decimal>,
+        pub trailing_delta: Option<u64>,
+        pub iceberg_qty: Option<Decimal>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        pub cancel_restrictions: Option<CancelRestrictions>,
+    };
+    Response => {
+        /// `SUCCESS`, `FAILURE` or `NOT_ATTEMPTED`.
+        pub cancel_result: String,
+        pub new_order_result: String,
+        pub cancel_response: CancelOrderResponse,
+        pub new_order_response: Option<OrderResponse>,
+    };
+}
+
+crate::define_request! {
+    Name => OpenOrders;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/openOrders";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 6 } else { 80 };
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => AllOrders;
+    Product => Product::Spot;
+    Method => Method::GET;
+    Endpoint => "/api/v3/allOrders";
+    Signed => true;
+    Weight => 20;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => NewOco;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/orderList/oco";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub list_client_order_id: Option<String>,
+        pub side: Side,
+        pub quantity: Decimal,
+        /// `STOP_LOSS_LIMIT`, `STOP_LOSS`, `LIMIT_MAKER`, `TAKE_PROFIT` or `TAKE_PROFIT_LIMIT`.
+        pub above_type: OrderType,
+        pub above_client_order_id: Option<String>,
+        pub above_iceberg_qty: Option<Decimal>,
+        pub above_price: Option<Decimal>,
+        pub above_stop_price: Option<Decimal>,
+        pub above_trailing_delta: Option<u64>,
+        pub above_time_in_force: Option<TimeInForce>,
+        pub below_type: OrderType,
+        pub below_client_order_id: Option<String>,
+        pub below_iceberg_qty: Option<Decimal>,
+        pub below_price: Option<Decimal>,
+        pub below_stop_price: Option<Decimal>,
+        pub below_trailing_delta: Option<u64>,
+        pub below_time_in_force: Option<TimeInForce>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    };
+    Response => OrderList;
+}
+
+crate::define_request! {
+    Name => NewOto;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/orderList/oto";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub list_client_order_id: Option<String>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        /// `LIMIT` or `LIMIT_MAKER`.
+        pub working_type: OrderType,
+        pub working_side: Side,
+        pub working_client_order_id: Option<String>,
+        pub working_price: Decimal,
+        pub working_quantity: Decimal,
+        pub working_iceberg_qty: Option<Decimal>,
+        pub working_time_in_force: Option<TimeInForce>,
+        pub pending_type: OrderType,
+        pub pending_side: Side,
+        pub pending_client_order_id: Option<String>,
+        pub pending_price: Option<Decimal>,
+        pub pending_stop_price: Option<Decimal>,
+        pub pending_trailing_delta: Option<u64>,
+        pub pending_quantity: Decimal,
+        pub pending_iceberg_qty: Option<Decimal>,
+        pub pending_time_in_force: Option<TimeInForce>,
+    };
+    Response => OrderList;
+}
+
+crate::define_request! {
+    Name => NewOtoco;
+    Product => Product::Spot;
+    Method => Method::POST;
+    Endpoint => "/api/v3/orderList/otoco";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub symbol: String,
+        pub list_client_order_id: Option<String>,
+        pub new_order_resp_type: Option<NewOrderResponseType>,
+        pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+        /// `LIMIT` or `LIMIT_MAKER`.
+        pub working_type: OrderType,
+        pub working_side: Side,
+        pub working_client_order_id: Option<String>,
+        pub working_price: Decimal,
+        pub working_quantity: Decimal,
+        pub working_iceberg_qty: Option<Decimal>,
+        pub working_time_in_force: Option<TimeInForce>,
+        pub pending_side: Side,
+        pub pending_quantity: Decimal,
+        pub pending_above_type: OrderType,
+        pub pending_above_client_order_id: Option<String>,
+        pub pending_above_price: Option<Decimal>,
+        pub pending_above_stop_price: Option<Decimal>,
+        pub pending_above_trailing_delta: Option<u64>,
+        pub pending_above_iceberg_qty: Option<Decimal>,
+        pub pending_above_time_in_force: Option<TimeInForce>,
+        pub pending_below_type: Option<OrderType>,
+        pub pending_below_client_order_id: Option<String>,
+        pub pending_below_price: Option<Decimal>,
+        pub pending_below_stop_price: Option<Decimal>,
+        pub pending_below_trailing_delta: Option<u64>,
+        pub pending_below_iceberg_qty: Option<Decimal>,
+        pub pending_below_time_in_force: Option<TimeInForce>,
+    };
+    Response => OrderList;
+}
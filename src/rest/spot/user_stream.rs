@@ -8,6 +8,7 @@ crate::define_request! {
     Endpoint => "/api/v3/userDataStream";
     Keyed => true;
     Signed => false;
+    Weight => 2;
     Request => {};
     Response => UserDataStream;
 }
@@ -19,6 +20,7 @@ crate::define_request! {
     Endpoint => "/api/v3/userDataStream";
     Keyed => true;
     Signed => false;
+    Weight => 2;
     Request => { pub listen_key: String };
     Response => {};
 }
@@ -30,6 +32,7 @@ crate::define_request! {
     Endpoint => "/api/v3/userDataStream";
     Keyed => true;
     Signed => false;
+    Weight => 2;
     Request => { pub listen_key: String };
     Response => {};
 }
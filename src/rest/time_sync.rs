@@ -0,0 +1,130 @@
+use crate::models::Product;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Number of server time queries made per synchronisation. The one with the shortest
+/// round trip wins, since its midpoint is the tightest estimate of the server clock.
+pub(crate) const SAMPLES: usize = 3;
+
+/// Tracks the offset between the local clock and the Binance server clock for each product.
+///
+/// Clones share the same offsets, so a cloned `Binance` benefits from a sync done by another one.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TimeSync {
+    refresh_interval: Option<Duration>,
+    offsets: Arc<Mutex<HashMap<Product, ClockOffset>>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ClockOffset {
+    offset: i64,
+    synced_at: Instant,
+}
+
+/// One server time query: local milliseconds before sending, after receiving, and the server time returned.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Sample {
+    pub sent_at: i64,
+    pub received_at: i64,
+    pub server_time: i64,
+}
+
+impl TimeSync {
+    pub fn enable(&mut self, refresh_interval: Duration) {
+        self.refresh_interval = Some(refresh_interval);
+    }
+
+    pub fn disable(&mut self) {
+        self.refresh_interval = None;
+        self.offsets.lock().unwrap().clear();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.refresh_interval.is_some()
+    }
+
+    /// Whether the offset of `product` is missing or older than the refresh interval.
+    pub fn needs_sync(&self, product: Product) -> bool {
+        let refresh_interval = match self.refresh_interval {
+            Some(interval) => interval,
+            None => return false,
+        };
+
+        match self.offsets.lock().unwrap().get(&clock_source(product)) {
+            Some(offset) => offset.synced_at.elapsed() >= refresh_interval,
+            None => true,
+        }
+    }
+
+    /// Milliseconds to add to the local clock to get the server clock, if a sync has happened.
+    pub fn offset(&self, product: Product) -> Option<i64> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.offsets
+            .lock()
+            .unwrap()
+            .get(&clock_source(product))
+            .map(|o| o.offset)
+    }
+
+    pub fn update(&self, product: Product, samples: &[Sample]) -> Option<i64> {
+        let offset = estimate_offset(samples)?;
+        self.offsets.lock().unwrap().insert(
+            clock_source(product),
+            ClockOffset {
+                offset,
+                synced_at: Instant::now(),
+            },
+        );
+        Some(offset)
+    }
+}
+
+/// The product whose server time endpoint is used to stamp requests for `product`.
+///
+/// Portfolio margin does not expose a usable time endpoint, so it borrows the clock of the
+/// USD-M matching engine. The `/sapi` services run on the spot clock.
+pub(crate) fn clock_source(product: Product) -> Product {
+    match product {
+        Product::PortfolioMargin => Product::UsdMFutures,
+        Product::Sapi => Product::Spot,
+        product => product,
+    }
+}
+
+fn estimate_offset(samples: &[Sample]) -> Option<i64> {
+    let best = samples
+        .iter()
+        .filter(|s| s.received_at >= s.sent_at)
+        .min_by_key(|s| s.received_at - s.sent_at)?;
+
+    let midpoint = best.sent_at + (best.received_at - best.sent_at) / 2;
+    Some(best.server_time - midpoint)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{estimate_offset, Sample};
+
+    #[test]
+    fn offset_uses_fastest_round_trip() {
+        let samples = [
+            Sample {
+                sent_at: 1000,
+                received_at: 1400,
+                server_time: 1900,
+            },
+            Sample {
+                sent_at: 2000,
+                received_at: 2020,
+                server_time: 2510,
+            },
+        ];
+        assert_eq!(estimate_offset(&samples), Some(500));
+        assert_eq!(estimate_offset(&[]), None);
+    }
+}
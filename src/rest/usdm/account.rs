@@ -1,9 +1,12 @@
-use crate::models::usdm::{Asset, Position};
+use crate::models::usdm::{Asset, MarginType, Position, PositionSide};
 use crate::models::Product;
 use crate::parser::string_or;
-use crate::parser::string_or_decimal;
+use crate::parser::{string_or_decimal, string_or_decimal_opt};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
 use reqwest::Method;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 crate::define_request! {
     Name => GetCurrentPositionMode;
@@ -11,6 +14,7 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/fapi/v1/positionSide/dual";
     Signed => true;
+    Weight => 30;
     Request => {};
     Response => {
         pub dual_side_position: bool,
@@ -23,6 +27,7 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/fapi/v2/account";
     Signed => true;
+    Weight => 5;
     Request => {};
     Response => {
         #[serde(with = "string_or_decimal")]
@@ -61,3 +66,333 @@ crate::define_request! {
         pub positions: Vec<Position>,
     };
 }
+
+crate::define_request! {
+    Name => ChangePositionMode;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/positionSide/dual";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        /// `true` for the hedge mode, `false` for the one-way mode.
+        pub dual_side_position: bool,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => GetMultiAssetsMode;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/multiAssetsMargin";
+    Signed => true;
+    Weight => 30;
+    Request => {};
+    Response => {
+        pub multi_assets_margin: bool,
+    };
+}
+
+crate::define_request! {
+    Name => ChangeMultiAssetsMode;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/multiAssetsMargin";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub multi_assets_margin: bool,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => AccountTrades;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/userTrades";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: String,
+        /// Only with `symbol`, `from_id` can't be combined with the times.
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub from_id: Option<u64>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<AccountTrade>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTrade {
+    pub id: u64,
+    pub symbol: String,
+    pub order_id: u64,
+    pub side: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quote_qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub realized_pnl: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub buyer: bool,
+    pub maker: bool,
+    pub time: u64,
+}
+
+crate::define_request! {
+    Name => IncomeHistory;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/income";
+    Signed => true;
+    Weight => 30;
+    Request => {
+        pub symbol: Option<String>,
+        /// e.g. `TRANSFER`, `REALIZED_PNL`, `FUNDING_FEE` or `COMMISSION`.
+        pub income_type: Option<String>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub page: Option<u64>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Income>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    /// Empty for the incomes not tied to a symbol, like transfers.
+    pub symbol: String,
+    pub income_type: String,
+    #[serde(with = "string_or_decimal")]
+    pub income: Decimal,
+    pub asset: String,
+    pub info: String,
+    pub time: u64,
+    pub tran_id: u64,
+    pub trade_id: String,
+}
+
+crate::define_request! {
+    Name => ChangeLeverage;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/leverage";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        /// From 1 to 125.
+        pub leverage: u64,
+    };
+    Response => {
+        pub symbol: String,
+        pub leverage: u64,
+        #[serde(with = "string_or_decimal")]
+        pub max_notional_value: Decimal,
+    };
+}
+
+crate::define_request! {
+    Name => ChangeMarginType;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/marginType";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub margin_type: MarginType,
+    };
+    Response => {
+        pub code: i64,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => ModifyIsolatedPositionMargin;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/positionMargin";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub position_side: Option<PositionSide>,
+        pub amount: Decimal,
+        /// 1 to add margin, 2 to reduce it.
+        pub r#type: u8,
+    };
+    Response => {
+        #[serde(with = "string_or_decimal")]
+        pub amount: Decimal,
+        pub code: i64,
+        pub msg: String,
+        pub r#type: u8,
+    };
+}
+
+crate::define_request! {
+    Name => PositionRisk;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v2/positionRisk";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<PositionRisk>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRisk {
+    pub symbol: String,
+    pub position_side: String,
+    #[serde(with = "string_or_decimal")]
+    pub position_amt: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub entry_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub break_even_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "unRealizedProfit", with = "string_or_decimal")]
+    pub unrealized_profit: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub liquidation_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub leverage: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub max_notional_value: Decimal,
+    /// `isolated` or `cross`.
+    pub margin_type: String,
+    #[serde(with = "string_or_decimal")]
+    pub isolated_margin: Decimal,
+    #[serde(with = "string_or")]
+    pub is_auto_add_margin: bool,
+    #[serde(with = "string_or_decimal")]
+    pub notional: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub isolated_wallet: Decimal,
+    pub update_time: u64,
+}
+
+crate::define_request! {
+    Name => LeverageBrackets;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/leverageBracket";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => LeverageBrackets;
+}
+
+/// The brackets of every symbol, a single `SymbolBrackets` when queried with `symbol`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum LeverageBrackets {
+    AllSymbolBrackets(Vec<SymbolBrackets>),
+    SingleSymbolBrackets(SymbolBrackets),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolBrackets {
+    pub symbol: String,
+    /// Only set when the user's brackets were adjusted.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub notional_coef: Option<Decimal>,
+    pub brackets: Vec<LeverageBracket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageBracket {
+    pub bracket: u64,
+    pub initial_leverage: u64,
+    #[serde(with = "string_or_decimal")]
+    pub notional_cap: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub notional_floor: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub maint_margin_ratio: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub cum: Decimal,
+}
+
+crate::define_request! {
+    Name => AdlQuantile;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/adlQuantile";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<AdlQuantile>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AdlQuantile {
+    pub symbol: String,
+    pub adl_quantile: AdlQuantiles,
+}
+
+/// The quantiles from 0 to 4 of the position sides, `both` is only set in one-way mode and
+/// `hedge` only in hedge mode.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct AdlQuantiles {
+    pub long: Option<u8>,
+    pub short: Option<u8>,
+    pub hedge: Option<u8>,
+    pub both: Option<u8>,
+}
+
+crate::define_request! {
+    Name => CommissionRate;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/commissionRate";
+    Signed => true;
+    Weight => 20;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub symbol: String,
+        #[serde(with = "string_or_decimal")]
+        pub maker_commission_rate: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub taker_commission_rate: Decimal,
+    };
+}
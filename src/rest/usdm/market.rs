@@ -1,17 +1,32 @@
-use crate::models::{AssetInformation, Filter, Product, RateLimit};
-use crate::parser::string_or_decimal;
+use crate::models::{
+    spot::{AggTrade, Asks, Bids, BookTickers, KlineSummary, Prices, WindowStatistics},
+    AssetInformation, Filter, Product, RateLimit, ServerTime,
+};
+use crate::parser::{string_or_decimal, string_or_decimal_opt};
 use chrono::serde::{ts_milliseconds, ts_milliseconds_option};
 use chrono::{DateTime, Utc};
 use reqwest::Method;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+crate::define_request! {
+    Name => ServerTime;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/time";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => ServerTime;
+}
+
 crate::define_request! {
     Name => ExchangeInformation;
     Product => Product::UsdMFutures;
     Method => Method::GET;
     Endpoint => "/fapi/v1/exchangeInfo";
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {
         pub timezone: String,
@@ -23,6 +38,33 @@ crate::define_request! {
     };
 }
 
+crate::define_request! {
+    Name => OrderBook;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/depth";
+    Signed => false;
+    Weight(req) => match req.limit.unwrap_or(500) {
+        0..=50 => 2,
+        51..=100 => 5,
+        101..=500 => 10,
+        _ => 20,
+    };
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+    };
+    Response => {
+        pub last_update_id: u64,
+        #[serde(rename = "E")]
+        pub message_output_time: u64,
+        #[serde(rename = "T")]
+        pub transaction_time: u64,
+        pub bids: Vec<Bids>,
+        pub asks: Vec<Asks>,
+    };
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
@@ -41,6 +83,7 @@ crate::define_request! {
     Method => Method::GET;
     Endpoint => "/fapi/v1/fundingRate";
     Signed => false;
+    Weight(req) => if req.symbol.is_some() { 1 } else { 10 };
     Request => {
         pub symbol: Option<String>,
         #[serde(with = "ts_milliseconds_option")]
@@ -61,3 +104,471 @@ pub struct FundingRate {
     #[serde(with = "ts_milliseconds")]
     pub funding_time: DateTime<Utc>,
 }
+
+crate::define_request! {
+    Name => Ping;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/ping";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {};
+}
+
+crate::define_request! {
+    Name => RecentTrades;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/trades";
+    Signed => false;
+    Weight => 5;
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Trade>;
+}
+
+crate::define_request! {
+    Name => HistoricalTrades;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/historicalTrades";
+    Keyed => true;
+    Signed => false;
+    Weight => 20;
+    Request => {
+        pub symbol: String,
+        pub limit: Option<u64>,
+        pub from_id: Option<u64>,
+    };
+    Response => Vec<Trade>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: u64,
+    #[serde(with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub quote_qty: Decimal,
+    pub time: u64,
+    pub is_buyer_maker: bool,
+}
+
+crate::define_request! {
+    Name => AggTrades;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/aggTrades";
+    Signed => false;
+    Weight => 20;
+    Request => {
+        pub symbol: String,
+        pub from_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<AggTrade>;
+}
+
+/// The weight of the kline requests, which grows with `limit`.
+fn kline_weight(limit: Option<u64>) -> u64 {
+    match limit.unwrap_or(500) {
+        0..=99 => 1,
+        100..=499 => 2,
+        500..=1000 => 5,
+        _ => 10,
+    }
+}
+
+crate::define_request! {
+    Name => Klines;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/klines";
+    Signed => false;
+    Weight(req) => kline_weight(req.limit);
+    Request => {
+        pub symbol: String,
+        /// e.g. `1m`, `1h`, `1d`, `1w` or `1M`.
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => ContinuousKlines;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/continuousKlines";
+    Signed => false;
+    Weight(req) => kline_weight(req.limit);
+    Request => {
+        pub pair: String,
+        /// `PERPETUAL`, `CURRENT_QUARTER` or `NEXT_QUARTER`.
+        pub contract_type: String,
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => IndexPriceKlines;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/indexPriceKlines";
+    Signed => false;
+    Weight(req) => kline_weight(req.limit);
+    Request => {
+        pub pair: String,
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    // Only the prices are meaningful, the volumes and trade counts are zero.
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => MarkPriceKlines;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/markPriceKlines";
+    Signed => false;
+    Weight(req) => kline_weight(req.limit);
+    Request => {
+        pub symbol: String,
+        pub interval: String,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    // Only the prices are meaningful, the volumes and trade counts are zero.
+    Response => Vec<KlineSummary>;
+}
+
+crate::define_request! {
+    Name => PremiumIndex;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/premiumIndex";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => PremiumIndexes;
+}
+
+/// The mark price and funding of a perpetual, a single `PremiumIndex` when queried with `symbol`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum PremiumIndexes {
+    AllPremiumIndexes(Vec<PremiumIndex>),
+    SinglePremiumIndex(PremiumIndex),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PremiumIndex {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub index_price: Decimal,
+    /// Only meaningful in the last hour before settlement.
+    #[serde(with = "string_or_decimal")]
+    pub estimated_settle_price: Decimal,
+    /// Empty for delivery contracts.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub last_funding_rate: Option<Decimal>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub interest_rate: Option<Decimal>,
+    pub next_funding_time: u64,
+    pub time: u64,
+}
+
+crate::define_request! {
+    Name => FundingInfo;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/fundingInfo";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => Vec<FundingInfo>;
+}
+
+/// Only the symbols with adjusted funding parameters are listed.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingInfo {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub adjusted_funding_rate_cap: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub adjusted_funding_rate_floor: Decimal,
+    pub funding_interval_hours: u64,
+}
+
+crate::define_request! {
+    Name => Ticker24hr;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/ticker/24hr";
+    Signed => false;
+    Weight(req) => if req.symbol.is_some() { 1 } else { 40 };
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => WindowStatistics;
+}
+
+crate::define_request! {
+    Name => PriceTicker;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v2/ticker/price";
+    Signed => false;
+    Weight(req) => if req.symbol.is_some() { 1 } else { 2 };
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Prices;
+}
+
+crate::define_request! {
+    Name => BookTicker;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/ticker/bookTicker";
+    Signed => false;
+    Weight(req) => if req.symbol.is_some() { 2 } else { 5 };
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => BookTickers;
+}
+
+crate::define_request! {
+    Name => OpenInterest;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/openInterest";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub symbol: String,
+        #[serde(with = "string_or_decimal")]
+        pub open_interest: Decimal,
+        pub time: u64,
+    };
+}
+
+crate::define_request! {
+    Name => OpenInterestHistory;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/futures/data/openInterestHist";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        /// `5m`, `15m`, `30m`, `1h`, `2h`, `4h`, `6h`, `12h` or `1d`.
+        pub period: String,
+        pub limit: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<OpenInterestHistory>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterestHistory {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub sum_open_interest: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub sum_open_interest_value: Decimal,
+    pub timestamp: u64,
+}
+
+crate::define_request! {
+    Name => TopLongShortAccountRatio;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/futures/data/topLongShortAccountRatio";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub period: String,
+        pub limit: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<LongShortRatio>;
+}
+
+crate::define_request! {
+    Name => TopLongShortPositionRatio;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/futures/data/topLongShortPositionRatio";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub period: String,
+        pub limit: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<LongShortRatio>;
+}
+
+/// The long and short shares of the top traders, by account or by position depending on the request.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LongShortRatio {
+    pub symbol: String,
+    #[serde(with = "string_or_decimal")]
+    pub long_short_ratio: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub long_account: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub short_account: Decimal,
+    pub timestamp: u64,
+}
+
+crate::define_request! {
+    Name => TakerBuySellVolume;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/futures/data/takerlongshortRatio";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub period: String,
+        pub limit: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<TakerBuySellVolume>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TakerBuySellVolume {
+    #[serde(with = "string_or_decimal")]
+    pub buy_sell_ratio: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub buy_vol: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub sell_vol: Decimal,
+    pub timestamp: u64,
+}
+
+crate::define_request! {
+    Name => Basis;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/futures/data/basis";
+    Signed => false;
+    Weight => 1;
+    Request => {
+        pub pair: String,
+        /// `CURRENT_QUARTER`, `NEXT_QUARTER` or `PERPETUAL`.
+        pub contract_type: String,
+        pub period: String,
+        pub limit: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<Basis>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Basis {
+    pub pair: String,
+    pub contract_type: String,
+    #[serde(with = "string_or_decimal")]
+    pub index_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub futures_price: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub basis: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub basis_rate: Decimal,
+    /// Empty for perpetuals.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub annualized_basis_rate: Option<Decimal>,
+    pub timestamp: u64,
+}
+
+crate::define_request! {
+    Name => IndexConstituents;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/constituents";
+    Signed => false;
+    Weight => 2;
+    Request => {
+        pub symbol: String,
+    };
+    Response => {
+        pub symbol: String,
+        pub time: u64,
+        pub constituents: Vec<Constituent>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Constituent {
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub price: Option<Decimal>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub weight: Option<Decimal>,
+}
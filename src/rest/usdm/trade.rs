@@ -1,14 +1,16 @@
 use crate::models::{
     spot::{CancelOrderResponse, CanceledOrder, NewOrderResponse, NewOrderResponseType},
-    usdm::PositionSide,
-    usdm::WorkingType,
+    usdm::{BatchOrderResult, Order, PositionSide, WorkingType},
     OrderType, Product, Side, TimeInForce,
 };
-use crate::parser::string_or;
+use crate::parser::{json_string, string_or, string_or_decimal};
+use crate::rest::ConfirmableOrder;
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
 use fehler::throw;
 use reqwest::Method;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 
 crate::define_request! {
     Name => NewOrder;
@@ -16,6 +18,8 @@ crate::define_request! {
     Method => Method::POST;
     Endpoint => "/fapi/v1/order";
     Signed => true;
+    Weight => 0;
+    IsOrder => true;
     Request => {
         pub symbol: String,
         pub side: Side,
@@ -37,12 +41,44 @@ crate::define_request! {
     Response => NewOrderResponse;
 }
 
+impl ConfirmableOrder for NewOrderRequest {
+    type Query = QueryOrderRequest;
+
+    fn query(&self) -> Option<QueryOrderRequest> {
+        Some(QueryOrderRequest {
+            symbol: self.symbol.clone(),
+            order_id: None,
+            orig_client_order_id: Some(self.new_client_order_id.clone()?),
+        })
+    }
+
+    fn confirmed(&self, order: &Order) -> NewOrderResponse {
+        order.into()
+    }
+}
+
+crate::define_request! {
+    Name => QueryOrder;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+    };
+    Response => Order;
+}
+
 crate::define_request! {
     Name => CancelOrder;
     Product => Product::UsdMFutures;
     Method => Method::DELETE;
     Endpoint => "/fapi/v1/order";
     Signed => true;
+    Weight => 1;
     Request => {
         pub symbol: String,
         pub order_id: Option<u64>,
@@ -57,6 +93,7 @@ crate::define_request! {
     Method => Method::DELETE;
     Endpoint => "/fapi/v1/batchOrders";
     Signed => true;
+    Weight => 1;
     Request => {
         pub symbol: String,
         pub order_id_list: Vec<u64>,
@@ -71,6 +108,7 @@ crate::define_request! {
     Method => Method::DELETE;
     Endpoint => "/fapi/v1/allOpenOrders";
     Signed => true;
+    Weight => 1;
     Request => {
         pub symbol: String,
     };
@@ -101,6 +139,7 @@ crate::define_request! {
     Method => Method::POST;
     Endpoint => "/fapi/v1/countdownCancelAll";
     Signed => true;
+    Weight => 10;
     Request => {
         pub symbol: String,
         pub countdown_time: u64,
@@ -111,3 +150,202 @@ crate::define_request! {
         pub countdown_time: u64,
     };
 }
+
+crate::define_request! {
+    Name => PlaceMultipleOrders;
+    Product => Product::UsdMFutures;
+    Method => Method::POST;
+    Endpoint => "/fapi/v1/batchOrders";
+    Signed => true;
+    Weight => 5;
+    IsOrder => true;
+    Request => {
+        /// At most 5 orders.
+        #[serde(with = "json_string")]
+        pub batch_orders: Vec<BatchOrder>,
+    };
+    Response => Vec<BatchOrderResult>;
+}
+
+/// An order of a `PlaceMultipleOrdersRequest`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOrder {
+    pub symbol: String,
+    pub side: Side,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_side: Option<PositionSide>,
+    pub r#type: OrderType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_client_order_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_rate: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_type: Option<WorkingType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_protect: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_order_resp_type: Option<NewOrderResponseType>,
+}
+
+crate::define_request! {
+    Name => ModifyOrder;
+    Product => Product::UsdMFutures;
+    Method => Method::PUT;
+    Endpoint => "/fapi/v1/order";
+    Signed => true;
+    Weight => 1;
+    IsOrder => true;
+    Request => {
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+        pub symbol: String,
+        pub side: Side,
+        pub quantity: Decimal,
+        pub price: Decimal,
+    };
+    Response => NewOrderResponse;
+}
+
+crate::define_request! {
+    Name => ModifyMultipleOrders;
+    Product => Product::UsdMFutures;
+    Method => Method::PUT;
+    Endpoint => "/fapi/v1/batchOrders";
+    Signed => true;
+    Weight => 5;
+    IsOrder => true;
+    Request => {
+        /// At most 5 orders.
+        #[serde(with = "json_string")]
+        pub batch_orders: Vec<ModifyBatchOrder>,
+    };
+    Response => Vec<BatchOrderResult>;
+}
+
+/// An order of a `ModifyMultipleOrdersRequest`, only `LIMIT` orders can be modified.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyBatchOrder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orig_client_order_id: Option<String>,
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: Decimal,
+    pub price: Decimal,
+}
+
+crate::define_request! {
+    Name => OrderAmendments;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/orderAmendment";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        pub orig_client_order_id: Option<String>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<OrderAmendment>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub amendment_id: u64,
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub time: u64,
+    pub amendment: Amendment,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Amendment {
+    pub price: AmendedValue,
+    pub orig_qty: AmendedValue,
+    /// How many times the order has been modified so far.
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AmendedValue {
+    #[serde(with = "string_or_decimal")]
+    pub before: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub after: Decimal,
+}
+
+crate::define_request! {
+    Name => OpenOrders;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/openOrders";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 1 } else { 40 };
+    Request => {
+        pub symbol: Option<String>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => AllOrders;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/allOrders";
+    Signed => true;
+    Weight => 5;
+    Request => {
+        pub symbol: String,
+        pub order_id: Option<u64>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Order>;
+}
+
+crate::define_request! {
+    Name => ForceOrders;
+    Product => Product::UsdMFutures;
+    Method => Method::GET;
+    Endpoint => "/fapi/v1/forceOrders";
+    Signed => true;
+    Weight(req) => if req.symbol.is_some() { 20 } else { 50 };
+    Request => {
+        pub symbol: Option<String>,
+        /// `LIQUIDATION` or `ADL`, both by default.
+        pub auto_close_type: Option<String>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => Vec<Order>;
+}
@@ -8,6 +8,7 @@ crate::define_request! {
     Endpoint => "/fapi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {
         pub listen_key: String,
@@ -21,6 +22,7 @@ crate::define_request! {
     Endpoint => "/fapi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {};
 }
@@ -32,6 +34,7 @@ crate::define_request! {
     Endpoint => "/fapi/v1/listenKey";
     Keyed => true;
     Signed => false;
+    Weight => 1;
     Request => {};
     Response => {};
 }
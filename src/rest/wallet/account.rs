@@ -0,0 +1,43 @@
+use crate::models::Product;
+use reqwest::Method;
+
+crate::define_request! {
+    Name => SystemStatus;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/system/status";
+    Signed => false;
+    Weight => 1;
+    Request => {};
+    Response => {
+        /// 0 when normal, 1 during system maintenance.
+        pub status: u8,
+        pub msg: String,
+    };
+}
+
+crate::define_request! {
+    Name => ApiKeyPermissions;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/account/apiRestrictions";
+    Signed => true;
+    Weight => 1;
+    Request => {};
+    Response => {
+        pub ip_restrict: bool,
+        pub create_time: u64,
+        pub enable_reading: bool,
+        pub enable_spot_and_margin_trading: bool,
+        pub enable_withdrawals: bool,
+        pub enable_internal_transfer: bool,
+        pub enable_margin: bool,
+        pub enable_futures: bool,
+        pub permits_universal_transfer: bool,
+        pub enable_vanilla_options: bool,
+        #[serde(default)]
+        pub enable_portfolio_margin_trading: bool,
+        /// Only set for keys with spot and margin trading enabled and no IP restriction.
+        pub trading_authority_expiration_time: Option<u64>,
+    };
+}
@@ -0,0 +1,266 @@
+use crate::models::Product;
+use crate::parser::{comma_separated, string_or_decimal, string_or_decimal_opt};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// The source and destination wallets of a universal transfer.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UniversalTransferType {
+    #[default]
+    MainUmfuture,
+    MainCmfuture,
+    MainMargin,
+    MainFunding,
+    MainOption,
+    MainPortfolioMargin,
+    UmfutureMain,
+    UmfutureMargin,
+    UmfutureFunding,
+    UmfutureOption,
+    CmfutureMain,
+    CmfutureMargin,
+    CmfutureFunding,
+    MarginMain,
+    MarginUmfuture,
+    MarginCmfuture,
+    MarginFunding,
+    MarginOption,
+    FundingMain,
+    FundingUmfuture,
+    FundingCmfuture,
+    FundingMargin,
+    FundingOption,
+    OptionMain,
+    OptionUmfuture,
+    OptionMargin,
+    OptionFunding,
+    PortfolioMarginMain,
+    /// Spot to an isolated margin account, needs `from_symbol`.
+    MainIsolatedMargin,
+    IsolatedMarginMain,
+    IsolatedMarginIsolatedMargin,
+}
+
+crate::define_request! {
+    Name => UniversalTransfer;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/asset/transfer";
+    Signed => true;
+    Weight => 900;
+    UidWeight => true;
+    Request => {
+        pub r#type: UniversalTransferType,
+        pub asset: String,
+        pub amount: Decimal,
+        /// The isolated margin symbol, only for isolated margin transfers.
+        pub from_symbol: Option<String>,
+        pub to_symbol: Option<String>,
+    };
+    Response => {
+        pub tran_id: u64,
+    };
+}
+
+crate::define_request! {
+    Name => UniversalTransferHistory;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/asset/transfer";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub r#type: UniversalTransferType,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        /// From 1.
+        pub current: Option<u64>,
+        pub size: Option<u64>,
+        pub from_symbol: Option<String>,
+        pub to_symbol: Option<String>,
+    };
+    Response => {
+        pub total: u64,
+        #[serde(default)]
+        pub rows: Vec<Transfer>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    pub r#type: UniversalTransferType,
+    /// `PENDING`, `CONFIRMED` or `FAILED`.
+    pub status: String,
+    pub tran_id: u64,
+    pub timestamp: u64,
+}
+
+crate::define_request! {
+    Name => AssetDividendRecord;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/asset/assetDividend";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        pub asset: Option<String>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub limit: Option<u64>,
+    };
+    Response => {
+        pub total: u64,
+        pub rows: Vec<Dividend>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Dividend {
+    pub id: u64,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    pub asset: String,
+    pub div_time: u64,
+    /// e.g. `BNB distribution`
+    pub en_info: String,
+    pub tran_id: u64,
+}
+
+crate::define_request! {
+    Name => DustLog;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/asset/dribblet";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => {
+        pub total: u64,
+        pub user_asset_dribblets: Vec<DustConversion>,
+    };
+}
+
+/// A dust conversion, which converts several assets to BNB at once.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DustConversion {
+    pub operate_time: u64,
+    #[serde(with = "string_or_decimal")]
+    pub total_transfered_amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub total_service_charge_amount: Decimal,
+    pub trans_id: u64,
+    pub user_asset_dribblet_details: Vec<DustDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DustDetail {
+    pub trans_id: u64,
+    pub from_asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub transfered_amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub service_charge_amount: Decimal,
+    pub operate_time: u64,
+}
+
+crate::define_request! {
+    Name => DustTransfer;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/asset/dust";
+    Signed => true;
+    Weight => 10;
+    UidWeight => true;
+    Request => {
+        /// The assets to convert to BNB.
+        #[serde(with = "comma_separated")]
+        pub asset: Vec<String>,
+    };
+    Response => {
+        #[serde(with = "string_or_decimal")]
+        pub total_service_charge: Decimal,
+        #[serde(with = "string_or_decimal")]
+        pub total_transfered: Decimal,
+        pub transfer_result: Vec<DustTransferResult>,
+    };
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DustTransferResult {
+    pub from_asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub transfered_amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub service_charge_amount: Decimal,
+    pub operate_time: u64,
+    pub tran_id: u64,
+}
+
+crate::define_request! {
+    Name => FundingWallet;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/asset/get-funding-asset";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub asset: Option<String>,
+        pub need_btc_valuation: Option<bool>,
+    };
+    Response => Vec<FundingAsset>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingAsset {
+    pub asset: String,
+    #[serde(with = "string_or_decimal")]
+    pub free: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub locked: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub freeze: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdrawing: Decimal,
+    /// Only set with `need_btc_valuation`.
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub btc_valuation: Option<Decimal>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::DustTransferRequest;
+
+    #[test]
+    fn dust_transfer_params() {
+        let req = DustTransferRequest {
+            asset: vec!["BTC".into(), "USDT".into()],
+        };
+        assert_eq!(serde_qs::to_string(&req).unwrap(), "asset=BTC%2CUSDT");
+    }
+}
@@ -0,0 +1,278 @@
+use crate::models::Product;
+use crate::parser::{comma_separated, string_or_decimal, string_or_decimal_opt};
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+crate::define_request! {
+    Name => AllCoinsInformation;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/capital/config/getall";
+    Signed => true;
+    Weight => 10;
+    Request => {};
+    Response => Vec<CoinInformation>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinInformation {
+    pub coin: String,
+    pub name: String,
+    pub deposit_all_enable: bool,
+    pub withdraw_all_enable: bool,
+    #[serde(with = "string_or_decimal")]
+    pub free: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub locked: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub freeze: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdrawing: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub ipoing: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub ipoable: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub storage: Decimal,
+    pub is_legal_money: bool,
+    pub trading: bool,
+    pub network_list: Vec<Network>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Network {
+    pub network: String,
+    pub coin: String,
+    pub name: String,
+    pub is_default: bool,
+    pub deposit_enable: bool,
+    pub withdraw_enable: bool,
+    #[serde(default)]
+    pub deposit_desc: String,
+    #[serde(default)]
+    pub withdraw_desc: String,
+    #[serde(default)]
+    pub special_tips: Option<String>,
+    #[serde(default)]
+    pub address_regex: String,
+    #[serde(default)]
+    pub memo_regex: String,
+    #[serde(with = "string_or_decimal")]
+    pub withdraw_fee: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdraw_min: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdraw_max: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub withdraw_integer_multiple: Decimal,
+    pub min_confirm: u64,
+    pub un_lock_confirm: u64,
+    pub same_address: bool,
+    #[serde(default)]
+    pub estimated_arrival_time: u64,
+    pub busy: bool,
+}
+
+crate::define_request! {
+    Name => DepositHistory;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/capital/deposit/hisrec";
+    Signed => true;
+    Weight => 1;
+    Request => {
+        pub coin: Option<String>,
+        /// 0 pending, 6 credited but cannot withdraw, 7 wrong deposit, 8 waiting user confirm,
+        /// 1 success and 2 rejected.
+        pub status: Option<u8>,
+        /// At most 90 days before `end_time`, 90 days ago by default.
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+        pub offset: Option<u64>,
+        pub limit: Option<u64>,
+        pub tx_id: Option<String>,
+    };
+    Response => Vec<Deposit>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Deposit {
+    pub id: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    pub coin: String,
+    pub network: String,
+    pub status: u8,
+    pub address: String,
+    #[serde(default)]
+    pub address_tag: String,
+    pub tx_id: String,
+    pub insert_time: u64,
+    /// 0 for external deposits, 1 for internal transfers.
+    pub transfer_type: u8,
+    /// e.g. `12/12`
+    pub confirm_times: String,
+    pub unlock_confirm: u64,
+    /// 0 for the spot wallet, 1 for the funding wallet.
+    pub wallet_type: u8,
+}
+
+crate::define_request! {
+    Name => DepositAddress;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/capital/deposit/address";
+    Signed => true;
+    Weight => 10;
+    Request => {
+        pub coin: String,
+        /// The default network of `coin` when unset.
+        pub network: Option<String>,
+        pub amount: Option<Decimal>,
+    };
+    Response => {
+        pub address: String,
+        pub coin: String,
+        pub tag: String,
+        pub url: String,
+    };
+}
+
+crate::define_request! {
+    Name => Withdraw;
+    Product => Product::Sapi;
+    Method => Method::POST;
+    Endpoint => "/sapi/v1/capital/withdraw/apply";
+    Signed => true;
+    Weight => 900;
+    UidWeight => true;
+    Request => {
+        pub coin: String,
+        /// A client id for the withdrawal.
+        pub withdraw_order_id: Option<String>,
+        pub network: Option<String>,
+        pub address: String,
+        /// The memo or tag, for coins that need one.
+        pub address_tag: Option<String>,
+        pub amount: Decimal,
+        /// Deduct the fee from `amount` for internal transfers.
+        pub transaction_fee_flag: Option<bool>,
+        pub name: Option<String>,
+        /// 0 for the spot wallet, 1 for the funding wallet.
+        pub wallet_type: Option<u8>,
+    };
+    Response => {
+        pub id: String,
+    };
+}
+
+crate::define_request! {
+    Name => WithdrawHistory;
+    Product => Product::Sapi;
+    Method => Method::GET;
+    Endpoint => "/sapi/v1/capital/withdraw/history";
+    Signed => true;
+    Weight => 18000;
+    UidWeight => true;
+    Request => {
+        pub coin: Option<String>,
+        pub withdraw_order_id: Option<String>,
+        /// 0 email sent, 2 awaiting approval, 3 rejected, 4 processing, 6 completed.
+        pub status: Option<u8>,
+        pub offset: Option<u64>,
+        pub limit: Option<u64>,
+        #[serde(skip_serializing_if = "Vec::is_empty", with = "comma_separated")]
+        pub id_list: Vec<String>,
+        /// At most 90 days before `end_time`, 90 days ago by default.
+        #[serde(with = "ts_milliseconds_option")]
+        pub start_time: Option<DateTime<Utc>>,
+        #[serde(with = "ts_milliseconds_option")]
+        pub end_time: Option<DateTime<Utc>>,
+    };
+    Response => Vec<Withdrawal>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub id: String,
+    #[serde(with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(with = "string_or_decimal")]
+    pub transaction_fee: Decimal,
+    pub coin: String,
+    pub status: u8,
+    pub address: String,
+    #[serde(default)]
+    pub tx_id: String,
+    /// e.g. `2019-10-12 11:12:02` in UTC.
+    pub apply_time: String,
+    pub network: String,
+    /// 0 for external withdrawals, 1 for internal transfers.
+    pub transfer_type: u8,
+    pub withdraw_order_id: Option<String>,
+    #[serde(default)]
+    pub info: String,
+    #[serde(default)]
+    pub confirm_no: u64,
+    pub wallet_type: u8,
+    #[serde(default)]
+    pub tx_key: String,
+    pub complete_time: Option<String>,
+    #[serde(default, with = "string_or_decimal_opt")]
+    pub complete_amount: Option<Decimal>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CoinInformation, Deposit, WithdrawHistoryRequest, Withdrawal};
+    use rust_decimal::Decimal;
+    use serde_json::from_str;
+
+    #[test]
+    fn withdraw_history_params() {
+        let req = WithdrawHistoryRequest {
+            coin: Some("USDT".into()),
+            ..Default::default()
+        };
+        assert_eq!(serde_qs::to_string(&req).unwrap(), "coin=USDT");
+
+        let req = WithdrawHistoryRequest {
+            id_list: vec!["a1".into(), "b2".into()],
+            ..Default::default()
+        };
+        assert_eq!(serde_qs::to_string(&req).unwrap(), "idList=a1%2Cb2");
+    }
+
+    #[test]
+    fn responses() {
+        let coins = r#"[{"coin":"BTC","depositAllEnable":true,"free":"0.08074558","freeze":"0.00000000","ipoable":"0.00000000","ipoing":"0.00000000","isLegalMoney":false,"locked":"0.00000000","name":"Bitcoin","networkList":[{"addressRegex":"^(bnb1)[0-9a-z]{38}$","coin":"BTC","depositDesc":"Wallet Maintenance, Deposit Suspended","depositEnable":false,"isDefault":false,"memoRegex":"^[0-9A-Za-z\\-_]{1,120}$","minConfirm":1,"name":"BEP2","network":"BNB","specialTips":"Both a MEMO and an Address are required to successfully deposit your BEP2-BTCB tokens to Binance.","unLockConfirm":0,"withdrawDesc":"Wallet Maintenance, Withdrawal Suspended","withdrawEnable":false,"withdrawFee":"0.00000220","withdrawIntegerMultiple":"0.00000001","withdrawMax":"9999999999.99999999","withdrawMin":"0.00000440","sameAddress":true,"estimatedArrivalTime":25,"busy":false}],"storage":"0.00000000","trading":true,"withdrawAllEnable":true,"withdrawing":"0.00000000"}]"#;
+        let coins: Vec<CoinInformation> = from_str(coins).unwrap();
+        assert_eq!(coins[0].free, Decimal::new(8074558, 8));
+        assert_eq!(coins[0].network_list[0].network, "BNB");
+        assert_eq!(coins[0].network_list[0].withdraw_fee, Decimal::new(220, 8));
+
+        let deposits = r#"[{"id":"769800519366885376","amount":"0.001","coin":"BNB","network":"BNB","status":1,"address":"bnb136ns6lfw4zs5hg4n85vdthaad7hq5m4gtkgf23","addressTag":"101764890","txId":"98A3EA560C6B3336D348B6C83F0F95ECE4F1F5919E94BD006E5BF3BF264FACFC","insertTime":1661493146000,"transferType":0,"confirmTimes":"1/1","unlockConfirm":0,"walletType":0}]"#;
+        let deposits: Vec<Deposit> = from_str(deposits).unwrap();
+        assert_eq!(deposits[0].amount, Decimal::new(1, 3));
+        assert_eq!(deposits[0].confirm_times, "1/1");
+
+        let withdrawals = r#"[{"id":"b6ae22b3aa844210a7041aee7589627c","amount":"8.91000000","transactionFee":"0.004","coin":"USDT","status":6,"address":"0x94df8b352de7f46f64b01d3666bf6e936e44ce60","txId":"0xb5ef8c13b968a406cc62a93a8bd80f9e9a906ef1b3fcf20a2e48573c17659268","applyTime":"2019-10-12 11:12:02","network":"ETH","transferType":0,"withdrawOrderId":"WITHDRAWtest123","info":"The address is not valid. Please confirm with the recipient","confirmNo":3,"walletType":1,"txKey":"","completeTime":"2023-03-23 16:52:41"},{"id":"156ec387f49b41df8724fa744fa82719","amount":"0.00150000","transactionFee":"0.00000000","coin":"BTC","status":6,"address":"1FZdVHtiBqMrWdjPyRPULCUceZPJ2WLCsB","txId":"60fd9007ebfddc753455f95fafa808c4302c836e4d1eebc5a132c36c1d8ac354","applyTime":"2019-09-24 12:43:45","network":"BTC","transferType":0,"info":"","confirmNo":2,"walletType":1,"txKey":"","completeTime":"2023-03-23 16:52:41"}]"#;
+        let withdrawals: Vec<Withdrawal> = from_str(withdrawals).unwrap();
+        assert_eq!(withdrawals[0].transaction_fee, Decimal::new(4, 3));
+        assert_eq!(
+            withdrawals[0].withdraw_order_id.as_deref(),
+            Some("WITHDRAWtest123")
+        );
+        assert_eq!(withdrawals[1].withdraw_order_id, None);
+        assert_eq!(withdrawals[1].status, 6);
+    }
+}
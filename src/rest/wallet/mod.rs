@@ -0,0 +1,7 @@
+mod account;
+mod asset;
+mod capital;
+
+pub use account::*;
+pub use asset::*;
+pub use capital::*;
@@ -0,0 +1,128 @@
+use super::spot::{
+    BalanceUpdate, ExecutionReport, ListStatus, ListenKeyExpired, OutboundAccountPosition,
+};
+use crate::{
+    error::BinanceError,
+    models::{spot::UserDataStream, Product},
+    parser::string_or_decimal,
+    rest::classic_margin::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+    },
+    websocket::{ParseMessage, UserDataMessage},
+};
+use fehler::throws;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// The events of a cross or isolated margin user data stream, which are the spot ones plus the
+/// margin level and liability changes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "e")]
+pub enum WebsocketMessage {
+    Ping,
+    #[serde(rename = "outboundAccountPosition")]
+    OutboundAccountPosition(OutboundAccountPosition),
+    #[serde(rename = "balanceUpdate")]
+    BalanceUpdate(BalanceUpdate),
+    #[serde(rename = "executionReport")]
+    ExecutionReport(Box<ExecutionReport>),
+    #[serde(rename = "listStatus")]
+    ListStatus(ListStatus),
+    #[serde(rename = "MARGIN_LEVEL_STATUS_CHANGE")]
+    MarginLevelStatusChange(MarginLevelStatusChange),
+    #[serde(rename = "USER_LIABILITY_CHANGE")]
+    UserLiabilityChange(UserLiabilityChange),
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired(ListenKeyExpired),
+}
+
+impl ParseMessage for WebsocketMessage {
+    const PRODUCT: Product = Product::Sapi;
+
+    #[throws(BinanceError)]
+    fn parse(_: &str, data: &str) -> Self {
+        serde_json::from_str(data)?
+    }
+
+    fn ping() -> Self {
+        Self::Ping
+    }
+}
+
+/// Only the cross margin stream is managed here, the isolated one needs a symbol to start.
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &UserDataStream) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(listen_key: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {
+            listen_key: listen_key.into(),
+        }
+    }
+
+    fn close(listen_key: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {
+            listen_key: listen_key.into(),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::ListenKeyExpired(_))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MarginLevelStatusChange {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub margin_level: Decimal,
+    /// `EXCESSIVE`, `NORMAL`, `MARGIN_CALL`, `PRE_LIQUIDATION` or `FORCE_LIQUIDATION`.
+    #[serde(rename = "s")]
+    pub margin_level_status: String,
+}
+
+/// A borrow or an interest accrual.
+#[derive(Debug, Deserialize, Clone)]
+pub struct UserLiabilityChange {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "a")]
+    pub asset: String,
+    /// `BORROW`
+    #[serde(rename = "t")]
+    pub liability_type: String,
+    #[serde(rename = "T")]
+    pub tx_id: u64,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub principal: Decimal,
+    #[serde(rename = "i", with = "string_or_decimal")]
+    pub interest: Decimal,
+}
+
+#[cfg(test)]
+mod test {
+    use super::WebsocketMessage;
+    use crate::websocket::ParseMessage;
+
+    #[test]
+    fn margin_events() {
+        let level =
+            r#"{"e":"MARGIN_LEVEL_STATUS_CHANGE","E":1706084443001,"l":"1.25","s":"MARGIN_CALL"}"#;
+        let liability = r#"{"e":"USER_LIABILITY_CHANGE","E":1706084443001,"a":"BTC","t":"BORROW","T":1352286576452864727,"p":"1.03453430","i":"0"}"#;
+
+        assert!(matches!(
+            WebsocketMessage::parse("", level),
+            Ok(WebsocketMessage::MarginLevelStatusChange(_))
+        ));
+        match WebsocketMessage::parse("", liability) {
+            Ok(WebsocketMessage::UserLiabilityChange(change)) => assert_eq!(change.asset, "BTC"),
+            message => panic!("unexpected {:?}", message),
+        }
+    }
+}
@@ -1,18 +1,61 @@
-use super::models::AggregateTrade;
+use super::{
+    models::AggregateTrade,
+    usdm::{
+        AccountUpdate, BookTicker, ContinuousKline, ContractInfo, DepthUpdate, ForceOrder, Kline,
+        KlineData, MiniTicker, Ticker,
+    },
+};
 use crate::{
     error::BinanceError::{self, *},
-    models::Product,
-    websocket::ParseMessage,
+    models::{ExecutionType, OrderStatus, OrderType, Product, Side, TimeInForce},
+    parser::{string_or_decimal, string_or_decimal_opt},
+    rest::coinm::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+        StartUserDataStreamResponse,
+    },
+    websocket::{ParseMessage, UserDataMessage},
 };
 use fehler::{throw, throws};
-use serde::Serialize;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use serde_json::from_str;
 
 #[derive(Debug, Clone, Serialize)]
 #[non_exhaustive]
 pub enum WebsocketMessage {
     Ping,
+    // User Data Stream
+    UserOrderUpdate(Box<OrderTradeUpdate>),
+    UserAccountUpdate(AccountUpdateEvent),
+    UserAccountConfigUpdate(AccountConfigUpdate),
+    UserMarginCall(MarginCall),
+    UserDataStreamExpired,
+    // Market Stream
     AggregateTrade(AggregateTrade),
+    IndexPrice(IndexPrice),
+    MarkPrice(MarkPrice),
+    MarkPriceAll(Vec<MarkPrice>),
+    Kline(Kline),
+    ContinuousKline(ContinuousKline),
+    IndexPriceKline(PriceKline),
+    MarkPriceKline(PriceKline),
+    MiniTicker(MiniTicker),
+    MiniTickerAll(Vec<MiniTicker>),
+    Ticker(Ticker),
+    TickerAll(Vec<Ticker>),
+    BookTicker(BookTicker),
+    ForceOrder(ForceOrder),
+    /// `<symbol>@depth<levels>`
+    PartialDepth(DepthUpdate),
+    /// `<symbol>@depth`, the diffs to maintain a local order book from.
+    DepthUpdate(DepthUpdate),
+    ContractInfo(ContractInfo),
+}
+
+#[derive(Deserialize)]
+struct EventType {
+    #[serde(rename = "e")]
+    event_type: String,
 }
 
 impl ParseMessage for WebsocketMessage {
@@ -21,40 +64,52 @@ impl ParseMessage for WebsocketMessage {
     #[throws(BinanceError)]
     fn parse(stream: &str, data: &str) -> Self {
         if stream.ends_with("@aggTrade") {
-            let value: AggregateTrade = from_str(data)?;
-            Self::AggregateTrade(value)
-        } else if stream.contains("@markPrice") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::AggregateTrade(from_str(data)?)
+        } else if stream.contains("@indexPriceKline_") {
+            Self::IndexPriceKline(from_str(data)?)
+        } else if stream.contains("@markPriceKline_") {
+            Self::MarkPriceKline(from_str(data)?)
+        } else if stream.contains("@indexPrice") {
+            Self::IndexPrice(from_str(data)?)
         } else if stream.starts_with("!markPrice@arr") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::MarkPriceAll(from_str(data)?)
+        } else if stream.contains("@markPrice") {
+            Self::MarkPrice(from_str(data)?)
         } else if stream.contains("@kline_") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::Kline(from_str(data)?)
         } else if stream.contains("@continuousKline_") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@miniTicker") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::ContinuousKline(from_str(data)?)
         } else if stream == "!miniTicker@arr" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@ticker") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::MiniTickerAll(from_str(data)?)
+        } else if stream.ends_with("@miniTicker") {
+            Self::MiniTicker(from_str(data)?)
         } else if stream == "!ticker@arr" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@bookTicker") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream == "!bookTicker" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@forceOrder") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream == "!forceOrder@arr" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@depth") || stream.contains("@depth@") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.contains("@depth") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@compositeIndex") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::TickerAll(from_str(data)?)
+        } else if stream.ends_with("@ticker") {
+            Self::Ticker(from_str(data)?)
+        } else if stream.ends_with("@bookTicker") || stream == "!bookTicker" {
+            Self::BookTicker(from_str(data)?)
+        } else if stream.ends_with("@forceOrder") || stream == "!forceOrder@arr" {
+            Self::ForceOrder(from_str(data)?)
+        } else if let Some((_, levels)) = stream.split_once("@depth") {
+            if levels.starts_with(|c: char| c.is_ascii_digit()) {
+                Self::PartialDepth(from_str(data)?)
+            } else {
+                Self::DepthUpdate(from_str(data)?)
+            }
         } else if stream == "!contractInfo" {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::ContractInfo(from_str(data)?)
+        } else if stream.len() == 64 {
+            // is a user stream
+            let event: EventType = from_str(data)?;
+            match event.event_type.as_ref() {
+                "ORDER_TRADE_UPDATE" => Self::UserOrderUpdate(from_str(data)?),
+                "ACCOUNT_UPDATE" => Self::UserAccountUpdate(from_str(data)?),
+                "ACCOUNT_CONFIG_UPDATE" => Self::UserAccountConfigUpdate(from_str(data)?),
+                "MARGIN_CALL" => Self::UserMarginCall(from_str(data)?),
+                "listenKeyExpired" => Self::UserDataStreamExpired,
+                _ => throw!(UserDataStreamEventNotImplemented(event.event_type)),
+            }
         } else {
             throw!(UnknownStream(stream.into()))
         }
@@ -64,3 +119,353 @@ impl ParseMessage for WebsocketMessage {
         Self::Ping
     }
 }
+
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &StartUserDataStreamResponse) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(_: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {}
+    }
+
+    fn close(_: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {}
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::UserDataStreamExpired)
+    }
+}
+
+/// `<pair>@indexPrice` or `<pair>@indexPrice@1s`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexPrice {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "i")]
+    pub pair: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub index_price: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPrice {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "P", with = "string_or_decimal")]
+    pub estimated_settle_price: Decimal,
+    #[serde(rename = "i", with = "string_or_decimal_opt", default)]
+    pub index_price: Option<Decimal>,
+    /// Empty for delivery contracts.
+    #[serde(rename = "r", with = "string_or_decimal_opt")]
+    pub funding_rate: Option<Decimal>,
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+/// `<pair>@indexPriceKline_<interval>` and `<symbol>@markPriceKline_<interval>`. The volume
+/// fields of `kline` are always zero and its trade ids are timestamps.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceKline {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "i", default)]
+    pub account_alias: String,
+    #[serde(rename = "o")]
+    pub order: OrderUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "ap", with = "string_or_decimal")]
+    pub average_price: Decimal,
+    #[serde(rename = "sp", with = "string_or_decimal")]
+    pub stop_price: Decimal,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub last_filled_qty: Decimal,
+    #[serde(rename = "z", with = "string_or_decimal")]
+    pub accumulated_filled_qty: Decimal,
+    #[serde(rename = "L", with = "string_or_decimal")]
+    pub last_filled_price: Decimal,
+    #[serde(rename = "ma")]
+    pub margin_asset: String,
+    /// Absent when no commission was charged.
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "n", with = "string_or_decimal_opt", default)]
+    pub commission: Option<Decimal>,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "t")]
+    pub trade_id: i64,
+    #[serde(rename = "rp", with = "string_or_decimal")]
+    pub realized_profit: Decimal,
+    #[serde(rename = "b", with = "string_or_decimal")]
+    pub bids_notional: Decimal,
+    #[serde(rename = "a", with = "string_or_decimal")]
+    pub asks_notional: Decimal,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "R")]
+    pub is_reduce_only: bool,
+    #[serde(rename = "wt")]
+    pub working_type: String,
+    #[serde(rename = "ot")]
+    pub original_order_type: String,
+    #[serde(rename = "ps")]
+    pub position_side: String,
+    #[serde(rename = "cp", default)]
+    pub close_all: bool,
+    #[serde(rename = "AP", with = "string_or_decimal_opt", default)]
+    pub activation_price: Option<Decimal>,
+    #[serde(rename = "cr", with = "string_or_decimal_opt", default)]
+    pub callback_rate: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "i", default)]
+    pub account_alias: String,
+    #[serde(rename = "a")]
+    pub update: AccountUpdate,
+}
+
+/// Leverage changes of a symbol.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountConfigUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "ac")]
+    pub config: LeverageUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeverageUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "l")]
+    pub leverage: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarginCall {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "i", default)]
+    pub account_alias: String,
+    /// Only pushed for cross margin.
+    #[serde(rename = "cw", with = "string_or_decimal_opt", default)]
+    pub cross_wallet_balance: Option<Decimal>,
+    #[serde(rename = "p")]
+    pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub position_side: String,
+    #[serde(rename = "pa", with = "string_or_decimal")]
+    pub position_amount: Decimal,
+    #[serde(rename = "mt")]
+    pub margin_type: String,
+    #[serde(rename = "iw", with = "string_or_decimal")]
+    pub isolated_wallet: Decimal,
+    #[serde(rename = "mp", with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "up", with = "string_or_decimal")]
+    pub unrealized_pnl: Decimal,
+    #[serde(rename = "mm", with = "string_or_decimal")]
+    pub maintenance_margin: Decimal,
+}
+
+#[cfg(test)]
+mod test {
+    use super::WebsocketMessage;
+    use crate::{models::OrderStatus, websocket::ParseMessage};
+    use rust_decimal::Decimal;
+
+    const LISTEN_KEY: &str = "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1";
+
+    #[test]
+    fn market_streams() {
+        let fixtures: [(&str, &str, fn(&WebsocketMessage) -> bool); 8] = [
+            (
+                "btcusd@indexPrice",
+                r#"{"e":"indexPriceUpdate","E":1591261236000,"i":"BTCUSD","p":"9636.57860000"}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::IndexPrice(p)
+                    if p.pair == "BTCUSD" && p.index_price == Decimal::new(963657860000, 8))
+                },
+            ),
+            (
+                "btcusd_201225@markPrice",
+                r#"{"e":"markPriceUpdate","E":1596095725000,"s":"BTCUSD_201225","p":"10934.62615417","P":"10962.17178236","i":"10933.62615417","r":"","T":0}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MarkPrice(p)
+                    if p.symbol == "BTCUSD_201225"
+                        && p.mark_price == Decimal::new(1093462615417, 8)
+                        && p.funding_rate.is_none())
+                },
+            ),
+            (
+                "btcusd@indexPriceKline_1m",
+                r#"{"e":"indexPrice_kline","E":1591267070033,"ps":"BTCUSD","k":{"t":1591267020000,"T":1591267079999,"s":"0","i":"1m","f":1591267020000,"L":1591267070000,"o":"9542.21900000","c":"9542.50440000","h":"9542.71640000","l":"9541.88310000","v":"0","n":60,"x":false,"q":"0","V":"0","Q":"0","B":"0"}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::IndexPriceKline(k)
+                    if k.pair == "BTCUSD"
+                        && k.kline.interval == "1m"
+                        && k.kline.close_price == Decimal::new(954250440000, 8))
+                },
+            ),
+            (
+                "btcusd_200626@markPriceKline_1m",
+                r#"{"e":"markPrice_kline","E":1591267398004,"ps":"BTCUSD","k":{"t":1591267380000,"T":1591267439999,"s":"BTCUSD_200626","i":"1m","f":1591267380000,"L":1591267398000,"o":"9539.67161333","c":"9540.82761333","h":"9540.82761333","l":"9539.66961333","v":"0","n":19,"x":false,"q":"0","V":"0","Q":"0","B":"0"}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MarkPriceKline(k)
+                    if k.kline.interval == "1m" && k.kline.number_of_trades == 19)
+                },
+            ),
+            (
+                "btcusd_200626@ticker",
+                r#"{"e":"24hrTicker","E":1591268262453,"s":"BTCUSD_200626","p":"-43.4","P":"-0.452","w":"0.00147974","c":"9548.5","Q":"2","o":"9591.9","h":"10000.0","l":"7000.0","v":"487850","q":"32968676323.46222700","O":1591181820000,"C":1591268262442,"F":512014,"L":615289,"n":103272}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Ticker(t)
+                    if t.symbol == "BTCUSD_200626" && t.last_price == Decimal::new(95485, 1))
+                },
+            ),
+            (
+                "btcusd_200626@bookTicker",
+                r#"{"e":"bookTicker","u":17242169,"s":"BTCUSD_200626","ps":"BTCUSD","b":"9548.1","B":"52","a":"9548.5","A":"11","T":1591268628155,"E":1591268628166}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::BookTicker(t)
+                    if t.best_bid == Decimal::new(95481, 1) && t.best_ask_qty == Decimal::new(11, 0))
+                },
+            ),
+            (
+                "btcusd_200925@forceOrder",
+                r#"{"e":"forceOrder","E":1591154240950,"o":{"s":"BTCUSD_200925","ps":"BTCUSD","S":"SELL","o":"LIMIT","f":"IOC","q":"1","p":"9425.5","ap":"9496.5","X":"FILLED","l":"1","z":"1","T":1591154240949}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::ForceOrder(f)
+                    if f.order.symbol == "BTCUSD_200925"
+                        && matches!(f.order.order_status, OrderStatus::Filled)
+                        && f.order.average_price == Decimal::new(94965, 1))
+                },
+            ),
+            (
+                "btcusd_200626@depth@100ms",
+                r#"{"e":"depthUpdate","E":1591270260907,"T":1591270260891,"s":"BTCUSD_200626","ps":"BTCUSD","U":17285681,"u":17285702,"pu":17285675,"b":[["9517.6","10"]],"a":[["9518.5","45"]]}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::DepthUpdate(d)
+                    if d.prev_final_update_id == 17285675
+                        && d.asks == vec![(Decimal::new(95185, 1), Decimal::new(45, 0))])
+                },
+            ),
+        ];
+
+        for (stream, data, check) in fixtures {
+            match WebsocketMessage::parse(stream, data) {
+                Ok(msg) => assert!(check(&msg), "{}: unexpected {:?}", stream, msg),
+                Err(e) => panic!("{}: {}", stream, e),
+            }
+        }
+    }
+
+    #[test]
+    fn user_data_stream() {
+        let fixtures: [(&str, fn(&WebsocketMessage) -> bool); 4] = [
+            (
+                r#"{"e":"ORDER_TRADE_UPDATE","E":1591274595442,"T":1591274595453,"i":"SfsR","o":{"s":"BTCUSD_200925","c":"TEST","S":"SELL","o":"TRAILING_STOP_MARKET","f":"GTC","q":"2","p":"0","ap":"0","sp":"9103.1","x":"NEW","X":"NEW","i":8888888,"l":"0","z":"0","L":"0","ma":"BTC","N":"BTC","n":"0","T":1591274595442,"t":0,"rp":"0","b":"0","a":"0","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"TRAILING_STOP_MARKET","ps":"LONG","cp":false,"AP":"9476.8","cr":"5.0","pP":false}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::UserOrderUpdate(u)
+                    if u.order.order_id == 8888888
+                        && matches!(u.order.order_status, OrderStatus::New)
+                        && u.order.stop_price == Decimal::new(91031, 1))
+                },
+            ),
+            (
+                r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"i":"SfsR","a":{"m":"ORDER","B":[{"a":"BTC","wb":"122624.12345678","cw":"100.12345678","bc":"50.12345678"}],"P":[{"s":"BTCUSD_200925","pa":"0","ep":"0.0","cr":"200","up":"0","mt":"isolated","iw":"0.00000000","ps":"BOTH"}]}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::UserAccountUpdate(u)
+                    if u.update.balances[0].asset == "BTC"
+                        && u.update.balances[0].wallet_balance == Decimal::new(12262412345678, 8)
+                        && u.update.positions[0].symbol == "BTCUSD_200925")
+                },
+            ),
+            (
+                r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSD_PERP","l":25}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::UserAccountConfigUpdate(u)
+                    if u.config.symbol == "BTCUSD_PERP" && u.config.leverage == 25)
+                },
+            ),
+            (
+                r#"{"e":"MARGIN_CALL","E":1587727187525,"i":"SfsR","cw":"3.16812045","p":[{"s":"BTCUSD_200925","ps":"LONG","pa":"132","mt":"CROSSED","iw":"0","mp":"9187.17127000","up":"-1.166074","mm":"1.614445"}]}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::UserMarginCall(m)
+                    if m.cross_wallet_balance == Some(Decimal::new(316812045, 8))
+                        && m.positions[0].maintenance_margin == Decimal::new(1614445, 6))
+                },
+            ),
+        ];
+
+        for (data, check) in fixtures {
+            match WebsocketMessage::parse(LISTEN_KEY, data) {
+                Ok(msg) => assert!(check(&msg), "{}: unexpected {:?}", data, msg),
+                Err(e) => panic!("{}: {}", data, e),
+            }
+        }
+        assert!(matches!(
+            WebsocketMessage::parse(LISTEN_KEY, r#"{"e":"listenKeyExpired","E":1576653824250}"#),
+            Ok(WebsocketMessage::UserDataStreamExpired)
+        ));
+    }
+}
@@ -1,5 +1,11 @@
 use crate::{
-    error::BinanceError, models::Product, parser::string_or_decimal, websocket::ParseMessage,
+    error::BinanceError,
+    models::{spot::UserDataStream, Product},
+    parser::string_or_decimal,
+    rest::margin::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+    },
+    websocket::{ParseMessage, UserDataMessage},
 };
 use fehler::throws;
 use rust_decimal::Decimal;
@@ -221,6 +227,28 @@ impl ParseMessage for WebsocketMessage {
     }
 }
 
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &UserDataStream) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(_: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {}
+    }
+
+    fn close(_: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {}
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::ListenKeyExpired { .. })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AccountConfig {
     #[serde(rename = "s")]
@@ -1,25 +1,47 @@
+pub mod classic_margin;
 pub mod coinm;
 pub mod margin;
 mod models;
+pub mod options;
+mod order_book;
+mod reconnect;
 pub mod spot;
 pub mod usdm;
+mod user_stream;
+mod ws_api;
+
+pub use self::order_book::{DepthDiff, LocalOrderBook, OrderBookManager};
+pub use self::reconnect::{
+    ConnectionHealth, ReconnectPolicy, ReconnectingWebsocket, WebsocketEvent,
+};
+pub use self::user_stream::{UserDataMessage, UserDataStream, KEEPALIVE_INTERVAL};
+pub use self::ws_api::{BinanceWsApi, SessionStatus, WsApiRequest};
 
 use crate::{
-    error::BinanceError::{self, *},
+    error::{
+        BinanceError::{self, *},
+        BinanceResponseError,
+    },
     models::Product,
     Config,
 };
 use fehler::{throw, throws};
-use futures::{stream::Stream, SinkExt, StreamExt};
+use futures::{channel::oneshot, stream::Stream, FutureExt, SinkExt, StreamExt};
 use log::debug;
-use serde::{Deserialize, Serialize};
-use serde_json::{from_str, value::RawValue};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{from_str, value::RawValue, Value};
 use std::{
+    collections::HashMap,
+    future::Future,
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    net::TcpStream,
+    time::{sleep_until, Sleep},
 };
-use tokio::net::TcpStream;
 use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use tungstenite::Message;
 
@@ -32,8 +54,25 @@ pub trait ParseMessage: Sized {
     fn ping() -> Self;
 }
 
+/// Keepalive behaviour of a websocket. Pings from the server are always answered with the same payload.
+#[derive(Debug, Clone, Default)]
+pub struct Heartbeat {
+    /// Send our own pings at this interval.
+    pub ping_interval: Option<Duration>,
+    /// Fail with `WebsocketTimeout` when no frame at all arrives for this long.
+    pub idle_timeout: Option<Duration>,
+    /// Surface server pings as `M::ping()`, for diagnostics only.
+    pub emit_pings: bool,
+}
+
 pub struct BinanceWebsocket<M> {
     stream: WSStream,
+    heartbeat: Heartbeat,
+    last_frame: Instant,
+    next_ping: Instant,
+    timer: Option<Pin<Box<Sleep>>>,
+    next_id: u64,
+    pending: HashMap<u64, oneshot::Sender<Result<Value, BinanceError>>>,
     _phantom: PhantomData<M>,
 }
 
@@ -70,37 +109,172 @@ where
             throw!(EmptyTopics)
         }
 
-        let base = match M::PRODUCT {
-            Product::Spot => &config.ws_endpoint,
-            Product::UsdMFutures => &config.usdm_futures_ws_endpoint,
-            Product::CoinMFutures => &config.coinm_futures_ws_endpoint,
-            Product::EuropeanOptions => &config.european_options_ws_endpoint,
-            Product::PortfolioMargin => &config.portfolio_margin_ws_endpoint,
-        };
+        let base = config.ws_endpoint(M::PRODUCT);
         let endpoint = &format!("{}/stream?streams={}", base, combined);
         debug!("ws endpoint: {endpoint:?}");
-        let (stream, _) = match connect_async(endpoint).await {
-            Ok(v) => v,
-            Err(tungstenite::Error::Http(ref http)) => throw!(StartWebsocketError(
-                http.status(),
-                String::from_utf8_lossy(http.body().as_deref().unwrap_or_default()).to_string()
-            )),
-            Err(e) => throw!(e),
-        };
+        let stream = connect(endpoint).await?;
+        let now = Instant::now();
         Self {
             stream,
+            heartbeat: Heartbeat::default(),
+            last_frame: now,
+            next_ping: now,
+            timer: None,
+            next_id: 1,
+            pending: HashMap::new(),
             _phantom: PhantomData,
         }
     }
 }
 
 impl<M> BinanceWebsocket<M> {
+    /// Pings are answered automatically while the websocket is polled, so this only flushes
+    /// a pending pong.
+    #[deprecated(note = "pings are answered automatically")]
     #[throws(BinanceError)]
     pub async fn pong(&mut self) {
-        self.stream.send(Message::Pong(vec![])).await?
+        self.stream.flush().await?
+    }
+
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        self.next_ping = Instant::now() + heartbeat.ping_interval.unwrap_or_default();
+        self.heartbeat = heartbeat;
+        self.timer = None;
+    }
+
+    /// Subscribe to more streams on this connection.
+    #[throws(BinanceError)]
+    pub async fn subscribe<I, S>(&mut self, topics: I) -> MethodResponse<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.call("SUBSCRIBE", topic_params(topics)).await?
+    }
+
+    #[throws(BinanceError)]
+    pub async fn unsubscribe<I, S>(&mut self, topics: I) -> MethodResponse<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.call("UNSUBSCRIBE", topic_params(topics)).await?
+    }
+
+    #[throws(BinanceError)]
+    pub async fn list_subscriptions(&mut self) -> MethodResponse<Vec<String>> {
+        self.call("LIST_SUBSCRIPTIONS", vec![]).await?
+    }
+
+    /// Only `combined` is supported by Binance. Setting it to `false` breaks parsing, since messages are expected
+    /// in the combined `{"stream": ..., "data": ...}` format.
+    #[throws(BinanceError)]
+    pub async fn set_property(&mut self, name: &str, value: Value) -> MethodResponse<()> {
+        self.call("SET_PROPERTY", vec![name.into(), value]).await?
+    }
+
+    #[throws(BinanceError)]
+    pub async fn get_property(&mut self, name: &str) -> MethodResponse<Value> {
+        self.call("GET_PROPERTY", vec![name.into()]).await?
+    }
+
+    /// Send a method frame. Its response is picked up while the websocket is polled as a `Stream`.
+    #[throws(BinanceError)]
+    async fn call<T>(&mut self, method: &str, params: Vec<Value>) -> MethodResponse<T> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let frame = serde_json::to_string(&MethodCall { method, params, id })?;
+        debug!("ws method call: {frame}");
+        self.stream.send(Message::Text(frame)).await?;
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.insert(id, tx);
+        MethodResponse::new(rx)
+    }
+}
+
+#[throws(BinanceError)]
+async fn connect(endpoint: &str) -> WSStream {
+    match connect_async(endpoint).await {
+        Ok((stream, _)) => stream,
+        Err(tungstenite::Error::Http(ref http)) => throw!(StartWebsocketError(
+            http.status(),
+            String::from_utf8_lossy(http.body().as_deref().unwrap_or_default()).to_string()
+        )),
+        Err(e) => throw!(e),
     }
 }
 
+fn topic_params<I, S>(topics: I) -> Vec<Value>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    topics.into_iter().map(|t| t.as_ref().into()).collect()
+}
+
+#[derive(Serialize)]
+struct MethodCall<'a> {
+    method: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    params: Vec<Value>,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct MethodReply {
+    id: u64,
+    #[serde(default)]
+    result: Value,
+    error: Option<BinanceResponseError>,
+}
+
+/// Resolves with the server's reply to a method frame like `SUBSCRIBE`.
+///
+/// The reply is read from the same connection as the market data, so this only resolves while the
+/// websocket keeps being polled as a `Stream`, e.g. next to it in a `select!`. Awaiting it alone
+/// while nothing polls the websocket never completes.
+pub struct MethodResponse<T> {
+    rx: oneshot::Receiver<Result<Value, BinanceError>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> MethodResponse<T> {
+    fn new(rx: oneshot::Receiver<Result<Value, BinanceError>>) -> Self {
+        Self {
+            rx,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// A response that resolves immediately with `result`.
+    pub(crate) fn ready(result: Value) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let _ = tx.send(Ok(result));
+        Self::new(rx)
+    }
+}
+
+impl<T> Future for MethodResponse<T>
+where
+    T: DeserializeOwned,
+{
+    type Output = Result<T, BinanceError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let result = match self.rx.poll_unpin(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Ok(result)) => result,
+            // The websocket was dropped before the reply arrived.
+            Poll::Ready(Err(_)) => Err(WebsocketClosed),
+        };
+        Poll::Ready(result.and_then(|v| Ok(serde_json::from_value(v)?)))
+    }
+}
+
+impl<T> Unpin for MethodResponse<T> {}
+
 #[derive(Deserialize)]
 struct MessageWithTopic<'a> {
     stream: String,
@@ -115,25 +289,93 @@ where
     type Item = Result<M, BinanceError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let c = match self.stream.poll_next_unpin(cx) {
-            Poll::Ready(Some(Ok(c))) => c,
-            Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
-            Poll::Pending => return Poll::Pending,
-            Poll::Ready(None) => return Poll::Ready(None),
-        };
-        let msg = match c {
-            Message::Text(msg) => msg,
-            Message::Ping(..) => return Poll::Ready(Some(Ok(M::ping()))),
-            Message::Binary(_) | Message::Frame(_) | Message::Pong(..) => return Poll::Pending,
-            Message::Close(_) => return Poll::Ready(None),
-        };
+        loop {
+            let c = match self.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(c))) => c,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Pending => return self.poll_heartbeat(cx).map(|e| Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+            };
+            self.last_frame = Instant::now();
 
-        let t: MessageWithTopic = match from_str(&msg) {
-            Ok(v) => v,
-            Err(e) => return Poll::Ready(Some(Err(e.into()))),
-        };
+            // tungstenite queues the pong for a ping itself and sends it on the next read.
+            let msg = match c {
+                Message::Text(msg) => msg,
+                Message::Ping(..) if self.heartbeat.emit_pings => {
+                    return Poll::Ready(Some(Ok(M::ping())))
+                }
+                Message::Ping(..) | Message::Binary(_) | Message::Frame(_) | Message::Pong(..) => {
+                    continue
+                }
+                Message::Close(_) => return Poll::Ready(None),
+            };
 
-        Poll::Ready(Some(M::parse(&t.stream, t.data.get())))
+            let t: MessageWithTopic = match from_str(&msg) {
+                Ok(v) => v,
+                Err(e) => match from_str::<MethodReply>(&msg) {
+                    Ok(reply) => {
+                        self.reply(reply);
+                        continue;
+                    }
+                    Err(_) => return Poll::Ready(Some(Err(e.into()))),
+                },
+            };
+
+            return Poll::Ready(Some(M::parse(&t.stream, t.data.get())));
+        }
+    }
+}
+
+impl<M> BinanceWebsocket<M> {
+    /// Send our own pings when due and resolve with an error once the connection went silent.
+    fn poll_heartbeat(&mut self, cx: &mut Context) -> Poll<BinanceError> {
+        loop {
+            let now = Instant::now();
+            let idle_deadline = self.heartbeat.idle_timeout.map(|t| self.last_frame + t);
+            if let (Some(deadline), Some(timeout)) = (idle_deadline, self.heartbeat.idle_timeout) {
+                if now >= deadline {
+                    return Poll::Ready(WebsocketTimeout(timeout));
+                }
+            }
+
+            if let Some(interval) = self.heartbeat.ping_interval {
+                if now >= self.next_ping {
+                    // A broken sink is reported by the next read, and a busy one just skips this ping.
+                    if let Poll::Ready(Ok(())) = self.stream.poll_ready_unpin(cx) {
+                        let _ = self.stream.start_send_unpin(Message::Ping(vec![]));
+                        let _ = self.stream.poll_flush_unpin(cx);
+                    }
+                    self.next_ping = now + interval;
+                }
+            }
+
+            let ping_deadline = self.heartbeat.ping_interval.map(|_| self.next_ping);
+            let deadline = match (idle_deadline, ping_deadline) {
+                (Some(a), Some(b)) => a.min(b),
+                (Some(d), None) | (None, Some(d)) => d,
+                (None, None) => return Poll::Pending,
+            };
+            let timer = self
+                .timer
+                .get_or_insert_with(|| Box::pin(sleep_until(deadline.into())));
+            timer.as_mut().reset(deadline.into());
+            if timer.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    fn reply(&mut self, reply: MethodReply) {
+        let tx = match self.pending.remove(&reply.id) {
+            Some(tx) => tx,
+            None => return debug!("ws reply to unknown request {}", reply.id),
+        };
+        let result = match reply.error {
+            Some(e) => Err(e.into()),
+            None => Ok(reply.result),
+        };
+        // The caller may have dropped the response.
+        let _ = tx.send(result);
     }
 }
 
@@ -143,3 +385,30 @@ enum Either<L, R> {
     Left(L),
     Right(R),
 }
+
+#[cfg(test)]
+mod test {
+    use super::{MethodCall, MethodReply};
+    use serde_json::{from_str, to_string};
+
+    #[test]
+    fn method_frames() {
+        let call = MethodCall {
+            method: "LIST_SUBSCRIPTIONS",
+            params: vec![],
+            id: 3,
+        };
+        assert_eq!(
+            to_string(&call).unwrap(),
+            r#"{"method":"LIST_SUBSCRIPTIONS","id":3}"#
+        );
+
+        let reply: MethodReply = from_str(r#"{"result":["btcusdt@aggTrade"],"id":3}"#).unwrap();
+        assert_eq!(reply.id, 3);
+        assert!(reply.error.is_none());
+
+        let reply: MethodReply =
+            from_str(r#"{"error":{"code":2,"msg":"Invalid request"},"id":4}"#).unwrap();
+        assert_eq!(reply.error.unwrap().code, 2);
+    }
+}
@@ -0,0 +1,532 @@
+use crate::{
+    error::BinanceError::{self, *},
+    models::{options::PositionSide, Product},
+    parser::{string_or, string_or_decimal},
+    rest::options::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+        StartUserDataStreamResponse,
+    },
+    websocket::{ParseMessage, UserDataMessage},
+};
+use fehler::{throw, throws};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub enum WebsocketMessage {
+    Ping,
+    // User Data Stream
+    UserOrderUpdate(OrderTradeUpdate),
+    UserAccountUpdate(AccountUpdate),
+    UserRiskLevelChange(RiskLevelChange),
+    UserDataStreamExpired,
+    // Market Stream
+    Trade(Trade),
+    IndexPrice(IndexPrice),
+    /// `<underlyingAsset>@markPrice` pushes every option on the underlying.
+    MarkPrice(Vec<MarkPrice>),
+    Kline(Kline),
+    Ticker(Box<Ticker>),
+    /// `<underlyingAsset>@ticker@<expirationDate>`
+    TickerByExpiration(Vec<Ticker>),
+    /// `<symbol>@depth<levels>`, options only push partial books.
+    Depth(Depth),
+}
+
+#[derive(Deserialize)]
+struct EventType {
+    #[serde(rename = "e")]
+    event_type: String,
+}
+
+impl ParseMessage for WebsocketMessage {
+    const PRODUCT: Product = Product::EuropeanOptions;
+
+    #[throws(BinanceError)]
+    fn parse(stream: &str, data: &str) -> Self {
+        if stream.ends_with("@trade") {
+            Self::Trade(from_str(data)?)
+        } else if stream.ends_with("@index") {
+            Self::IndexPrice(from_str(data)?)
+        } else if stream.ends_with("@markPrice") {
+            Self::MarkPrice(from_str(data)?)
+        } else if stream.contains("@kline_") {
+            Self::Kline(from_str(data)?)
+        } else if stream.contains("@ticker@") {
+            Self::TickerByExpiration(from_str(data)?)
+        } else if stream.ends_with("@ticker") {
+            Self::Ticker(from_str(data)?)
+        } else if stream.contains("@depth") {
+            Self::Depth(from_str(data)?)
+        } else if stream.contains("@openInterest@") || stream == "option_pair" {
+            throw!(StreamNotImplemented(stream.into()))
+        } else if stream.len() == 64 {
+            // is a user stream
+            let event: EventType = from_str(data)?;
+            match event.event_type.as_ref() {
+                "ORDER_TRADE_UPDATE" => Self::UserOrderUpdate(from_str(data)?),
+                "ACCOUNT_UPDATE" => Self::UserAccountUpdate(from_str(data)?),
+                "RISK_LEVEL_CHANGE" => Self::UserRiskLevelChange(from_str(data)?),
+                "listenKeyExpired" => Self::UserDataStreamExpired,
+                _ => throw!(UserDataStreamEventNotImplemented(event.event_type)),
+            }
+        } else {
+            throw!(UnknownStream(stream.into()))
+        }
+    }
+
+    fn ping() -> Self {
+        Self::Ping
+    }
+}
+
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &StartUserDataStreamResponse) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(_: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {}
+    }
+
+    fn close(_: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {}
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::UserDataStreamExpired)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Trade {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "t", with = "string_or")]
+    pub trade_id: u64,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(rename = "b")]
+    pub buyer_order_id: u64,
+    #[serde(rename = "a")]
+    pub seller_order_id: u64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    /// `1` when the taker bought, `-1` when the taker sold.
+    #[serde(rename = "S", with = "string_or")]
+    pub direction: i8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexPrice {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub underlying: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub index_price: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPrice {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "mp", with = "string_or_decimal")]
+    pub mark_price: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Kline {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KlineData {
+    #[serde(rename = "t")]
+    pub start_time: u64,
+    #[serde(rename = "T")]
+    pub end_time: u64,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "F")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "o", with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub close_price: Decimal,
+    #[serde(rename = "h", with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub volume: Decimal,
+    #[serde(rename = "n")]
+    pub number_of_trades: u64,
+    #[serde(rename = "x")]
+    pub is_closed: bool,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(rename = "V", with = "string_or_decimal")]
+    pub taker_volume: Decimal,
+    #[serde(rename = "Q", with = "string_or_decimal")]
+    pub taker_amount: Decimal,
+}
+
+/// The rolling 24hr statistics of an option along with its implied volatilities and greeks.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ticker {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "o", with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(rename = "h", with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub last_price: Decimal,
+    #[serde(rename = "V", with = "string_or_decimal")]
+    pub volume: Decimal,
+    #[serde(rename = "A", with = "string_or_decimal")]
+    pub amount: Decimal,
+    #[serde(rename = "P", with = "string_or_decimal")]
+    pub price_change_percent: Decimal,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price_change: Decimal,
+    #[serde(rename = "Q", with = "string_or_decimal")]
+    pub last_qty: Decimal,
+    #[serde(rename = "F", with = "string_or")]
+    pub first_trade_id: u64,
+    #[serde(rename = "L", with = "string_or")]
+    pub last_trade_id: u64,
+    #[serde(rename = "n")]
+    pub number_of_trades: u64,
+    #[serde(rename = "bo", with = "string_or_decimal")]
+    pub best_bid: Decimal,
+    #[serde(rename = "ao", with = "string_or_decimal")]
+    pub best_ask: Decimal,
+    #[serde(rename = "bq", with = "string_or_decimal")]
+    pub best_bid_qty: Decimal,
+    #[serde(rename = "aq", with = "string_or_decimal")]
+    pub best_ask_qty: Decimal,
+    #[serde(rename = "b", with = "string_or_decimal")]
+    pub bid_iv: Decimal,
+    #[serde(rename = "a", with = "string_or_decimal")]
+    pub ask_iv: Decimal,
+    #[serde(rename = "d", with = "string_or_decimal")]
+    pub delta: Decimal,
+    #[serde(rename = "t", with = "string_or_decimal")]
+    pub theta: Decimal,
+    #[serde(rename = "g", with = "string_or_decimal")]
+    pub gamma: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub vega: Decimal,
+    #[serde(rename = "vo", with = "string_or_decimal")]
+    pub mark_iv: Decimal,
+    #[serde(rename = "mp", with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "hl", with = "string_or_decimal")]
+    pub high_price_limit: Decimal,
+    #[serde(rename = "ll", with = "string_or_decimal")]
+    pub low_price_limit: Decimal,
+    #[serde(rename = "eep", with = "string_or_decimal")]
+    pub estimated_strike_price: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Depth {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "pu")]
+    pub prev_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<(Decimal, Decimal)>,
+    #[serde(rename = "a")]
+    pub asks: Vec<(Decimal, Decimal)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "o")]
+    pub orders: Vec<OrderUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderUpdate {
+    #[serde(rename = "T")]
+    pub create_time: u64,
+    #[serde(rename = "t")]
+    pub update_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "oid", with = "string_or")]
+    pub order_id: u64,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    /// Negative for sell orders.
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(rename = "r")]
+    pub reduce_only: bool,
+    #[serde(rename = "po")]
+    pub post_only: bool,
+    #[serde(rename = "S")]
+    pub status: String,
+    #[serde(rename = "e", with = "string_or_decimal")]
+    pub executed_qty: Decimal,
+    #[serde(rename = "ec", with = "string_or_decimal")]
+    pub executed_cost: Decimal,
+    #[serde(rename = "f", with = "string_or_decimal")]
+    pub fee: Decimal,
+    #[serde(rename = "fi", default)]
+    pub fills: Vec<OrderFill>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderFill {
+    #[serde(rename = "t", with = "string_or")]
+    pub trade_id: u64,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub qty: Decimal,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    /// `MAKER` or `TAKER`.
+    #[serde(rename = "m")]
+    pub liquidity: String,
+    #[serde(rename = "f", with = "string_or_decimal")]
+    pub fee: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "B")]
+    pub balances: Vec<AccountUpdateBalance>,
+    #[serde(rename = "G")]
+    pub greeks: Vec<AccountUpdateGreeks>,
+    #[serde(rename = "P")]
+    pub positions: Vec<AccountUpdatePosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdateBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "b", with = "string_or_decimal")]
+    pub balance: Decimal,
+    #[serde(rename = "m", with = "string_or_decimal")]
+    pub position_value: Decimal,
+    #[serde(rename = "u", with = "string_or_decimal")]
+    pub unrealized_pnl: Decimal,
+    #[serde(rename = "U", with = "string_or_decimal")]
+    pub discounted_unrealized_pnl: Decimal,
+    #[serde(rename = "M", with = "string_or_decimal")]
+    pub maintenance_margin: Decimal,
+    #[serde(rename = "i", with = "string_or_decimal")]
+    pub initial_margin: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdateGreeks {
+    #[serde(rename = "ui")]
+    pub underlying: String,
+    #[serde(rename = "d", with = "string_or_decimal")]
+    pub delta: Decimal,
+    #[serde(rename = "t", with = "string_or_decimal")]
+    pub theta: Decimal,
+    #[serde(rename = "g", with = "string_or_decimal")]
+    pub gamma: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub vega: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdatePosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// Negative for short positions.
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub quantity: Decimal,
+    #[serde(rename = "r", with = "string_or_decimal")]
+    pub reducible_qty: Decimal,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub position_value: Decimal,
+    #[serde(rename = "a", with = "string_or_decimal")]
+    pub average_price: Decimal,
+}
+
+impl AccountUpdatePosition {
+    pub fn side(&self) -> PositionSide {
+        if self.quantity.is_sign_negative() {
+            PositionSide::Short
+        } else {
+            PositionSide::Long
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RiskLevelChange {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    /// `NORMAL`, `REDUCE_ONLY` and so on.
+    #[serde(rename = "s")]
+    pub risk_level: String,
+    #[serde(rename = "mb", with = "string_or_decimal")]
+    pub margin_balance: Decimal,
+    #[serde(rename = "mm", with = "string_or_decimal")]
+    pub maintenance_margin: Decimal,
+}
+
+#[cfg(test)]
+mod test {
+    use super::WebsocketMessage;
+    use crate::websocket::ParseMessage;
+    use rust_decimal::Decimal;
+
+    const LISTEN_KEY: &str = "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1";
+
+    #[test]
+    fn market_streams() {
+        let fixtures: [(&str, &str, fn(&WebsocketMessage) -> bool); 6] = [
+            (
+                "BTC-200630-9000-P@trade",
+                r#"{"e":"trade","E":1591677941092,"s":"BTC-200630-9000-P","t":"315","p":"4.0","q":"-0.0001","b":4611781675939004417,"a":4611781675939004418,"T":1591677567872,"S":"-1"}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Trade(t)
+                    if t.trade_id == 315 && t.price == Decimal::new(4, 0) && t.direction == -1)
+                },
+            ),
+            (
+                "ETHUSDT@index",
+                r#"{"e":"index","E":1661415480351,"s":"ETHUSDT","p":"1707.89008607"}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::IndexPrice(p)
+                    if p.underlying == "ETHUSDT" && p.index_price == Decimal::new(170789008607, 8))
+                },
+            ),
+            (
+                "ETH@markPrice",
+                r#"[{"e":"markPrice","E":1663684594227,"s":"ETH-220930-1500-C","mp":"30.3"}]"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MarkPrice(p)
+                    if p.len() == 1 && p[0].symbol == "ETH-220930-1500-C" && p[0].mark_price == Decimal::new(303, 1))
+                },
+            ),
+            (
+                "BTC-200630-9000-P@kline_1m",
+                r#"{"e":"kline","E":1638747660000,"s":"BTC-200630-9000-P","k":{"t":1638747660000,"T":1638747719999,"s":"BTC-200630-9000-P","i":"1m","F":0,"L":0,"o":"1000","c":"1000","h":"1000","l":"1000","v":"0","n":0,"x":false,"q":"0","V":"0","Q":"0"}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Kline(k)
+                    if k.kline.interval == "1m" && k.kline.close_price == Decimal::new(1000, 0))
+                },
+            ),
+            (
+                "BTC-200630-9000-P@ticker",
+                r#"{"e":"24hrTicker","E":1657706425200,"T":1657706425220,"s":"BTC-220930-18000-C","o":"2000","h":"2020","l":"2000","c":"2020","V":"1.42","A":"2841","P":"0.01","p":"20","Q":"0.01","F":"27","L":"48","n":22,"bo":"2012","ao":"2020","bq":"4.9","aq":"0.03","b":"0.4","a":"0.4","d":"0.61","t":"-0.78","g":"0.0001","v":"33.64","vo":"0.46","mp":"2003.5","hl":"2023.5","ll":"1983.5","eep":"0"}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Ticker(t)
+                    if t.symbol == "BTC-220930-18000-C"
+                        && t.best_bid == Decimal::new(2012, 0)
+                        && t.delta == Decimal::new(61, 2)
+                        && t.theta == Decimal::new(-78, 2)
+                        && t.mark_price == Decimal::new(20035, 1))
+                },
+            ),
+            (
+                "BTC-200630-9000-P@depth10@100ms",
+                r#"{"e":"depth","E":1591695934010,"T":1591695934000,"s":"BTC-200630-9000-P","u":162,"pu":162,"b":[["0.0001","1000"]],"a":[["0.0002","1000"]]}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Depth(d)
+                    if d.update_id == 162
+                        && d.bids == vec![(Decimal::new(1, 4), Decimal::new(1000, 0))])
+                },
+            ),
+        ];
+
+        for (stream, data, check) in fixtures {
+            match WebsocketMessage::parse(stream, data) {
+                Ok(msg) => assert!(check(&msg), "{}: unexpected {:?}", stream, msg),
+                Err(e) => panic!("{}: {}", stream, e),
+            }
+        }
+    }
+
+    #[test]
+    fn user_data_stream() {
+        let data = r#"{"e":"ACCOUNT_UPDATE","E":1591696384141,"B":[{"b":"100007992.26053177","m":"0","u":"458.782655111111","U":-9.8,"M":"-15452.328456","i":"-18852.328456","a":"USDT"}],"G":[{"ui":"SOLUSDT","d":-33.2933905,"t":35.5963,"g":-13.3929,"v":-0.1587}],"P":[{"s":"SOL-220912-35-C","c":"-50","r":"-50","p":"-100","a":"32.31"}],"uid":1000006559949}"#;
+        match WebsocketMessage::parse(LISTEN_KEY, data).unwrap() {
+            WebsocketMessage::UserAccountUpdate(u) => {
+                assert_eq!(u.balances[0].asset, "USDT");
+                assert_eq!(u.balances[0].balance, Decimal::new(10000799226053177, 8));
+                assert_eq!(u.greeks[0].underlying, "SOLUSDT");
+                assert_eq!(u.greeks[0].delta, Decimal::new(-332933905, 7));
+                assert_eq!(u.greeks[0].vega, Decimal::new(-1587, 4));
+                assert_eq!(u.positions[0].symbol, "SOL-220912-35-C");
+                assert_eq!(u.positions[0].quantity, Decimal::new(-50, 0));
+                assert_eq!(u.positions[0].average_price, Decimal::new(3231, 2));
+            }
+            msg => panic!("unexpected {:?}", msg),
+        }
+
+        let data = r#"{"e":"ORDER_TRADE_UPDATE","E":1657613775883,"o":[{"T":1657613342918,"t":1657613342918,"s":"BTC-220930-18000-C","c":"","oid":"4611869636869226548","p":"1993","q":"1","stp":0,"r":false,"po":true,"S":"PARTIALLY_FILLED","e":"0.1","ec":"199.3","f":"2","tif":"GTC","oty":"LIMIT","fi":[{"t":"20","p":"1993","q":"0.1","T":1657613774336,"m":"TAKER","f":"0.0002"}]}]}"#;
+        match WebsocketMessage::parse(LISTEN_KEY, data).unwrap() {
+            WebsocketMessage::UserOrderUpdate(u) => {
+                assert_eq!(u.orders.len(), 1);
+                let order = &u.orders[0];
+                assert_eq!(order.order_id, 4611869636869226548);
+                assert_eq!(order.status, "PARTIALLY_FILLED");
+                assert!(order.post_only);
+                assert_eq!(order.executed_qty, Decimal::new(1, 1));
+                assert_eq!(order.fills[0].trade_id, 20);
+                assert_eq!(order.fills[0].liquidity, "TAKER");
+            }
+            msg => panic!("unexpected {:?}", msg),
+        }
+
+        let data = r#"{"e":"RISK_LEVEL_CHANGE","E":1587727187525,"s":"REDUCE_ONLY","mb":"1534.11708371","mm":"254789.11708371"}"#;
+        match WebsocketMessage::parse(LISTEN_KEY, data).unwrap() {
+            WebsocketMessage::UserRiskLevelChange(r) => {
+                assert_eq!(r.risk_level, "REDUCE_ONLY");
+                assert_eq!(r.margin_balance, Decimal::new(153411708371, 8));
+            }
+            msg => panic!("unexpected {:?}", msg),
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use crate::{
+    error::BinanceError::{self, *},
+    models::{Product, Side},
+    rest::{spot, usdm, Binance},
+    websocket::{self, spot::DepthUpdate},
+};
+use fehler::{throw, throws};
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// The wait before fetching another snapshot after one turned out to be outdated, doubled on
+/// each further outdated snapshot. A depth 1000 snapshot weighs 50 on spot.
+const SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// One event of a diff depth stream with parsed price levels. A quantity of zero removes the level.
+#[derive(Debug, Clone)]
+pub struct DepthDiff {
+    pub symbol: String,
+    pub first_update_id: u64,
+    pub final_update_id: u64,
+    /// The `pu` field of futures streams: the final update id of the previous event.
+    pub prev_final_update_id: Option<u64>,
+    pub bids: Vec<(Decimal, Decimal)>,
+    pub asks: Vec<(Decimal, Decimal)>,
+}
+
+impl TryFrom<&DepthUpdate> for DepthDiff {
+    type Error = BinanceError;
+
+    #[throws(BinanceError)]
+    fn try_from(u: &DepthUpdate) -> Self {
+        DepthDiff {
+            symbol: u.symbol.clone(),
+            first_update_id: u.first_update_id,
+            final_update_id: u.final_update_id,
+            prev_final_update_id: None,
+            bids: parse_levels(&u.bids)?,
+            asks: parse_levels(&u.asks)?,
+        }
+    }
+}
+
+impl From<&websocket::usdm::DepthUpdate> for DepthDiff {
+    fn from(u: &websocket::usdm::DepthUpdate) -> Self {
+        DepthDiff {
+            symbol: u.symbol.clone(),
+            first_update_id: u.first_update_id,
+            final_update_id: u.final_update_id,
+            prev_final_update_id: Some(u.prev_final_update_id),
+            bids: u.bids.clone(),
+            asks: u.asks.clone(),
+        }
+    }
+}
+
+#[throws(BinanceError)]
+fn parse_levels(levels: &[(String, String)]) -> Vec<(Decimal, Decimal)> {
+    let mut parsed = Vec::with_capacity(levels.len());
+    for (price, qty) in levels {
+        let price = Decimal::from_str(price).map_err(|e| InvalidDepth(e.to_string()))?;
+        let qty = Decimal::from_str(qty).map_err(|e| InvalidDepth(e.to_string()))?;
+        parsed.push((price, qty));
+    }
+    parsed
+}
+
+/// The price levels of one symbol.
+#[derive(Debug, Clone, Default)]
+pub struct LocalOrderBook {
+    last_update_id: u64,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+}
+
+impl LocalOrderBook {
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+
+    pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids.iter().next_back().map(|(p, q)| (*p, *q))
+    }
+
+    pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks.iter().next().map(|(p, q)| (*p, *q))
+    }
+
+    /// The quantity at `price`, zero when there is no such level.
+    pub fn depth_at(&self, side: Side, price: Decimal) -> Decimal {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        levels.get(&price).copied().unwrap_or_default()
+    }
+
+    /// Bids from the best (highest) price down.
+    pub fn bids(&self) -> impl Iterator<Item = (Decimal, Decimal)> + '_ {
+        self.bids.iter().rev().map(|(p, q)| (*p, *q))
+    }
+
+    /// Asks from the best (lowest) price up.
+    pub fn asks(&self) -> impl Iterator<Item = (Decimal, Decimal)> + '_ {
+        self.asks.iter().map(|(p, q)| (*p, *q))
+    }
+
+    fn apply(&mut self, diff: &DepthDiff) {
+        fn update(levels: &mut BTreeMap<Decimal, Decimal>, changes: &[(Decimal, Decimal)]) {
+            for (price, qty) in changes {
+                if qty.is_zero() {
+                    levels.remove(price);
+                } else {
+                    levels.insert(*price, *qty);
+                }
+            }
+        }
+
+        update(&mut self.bids, &diff.bids);
+        update(&mut self.asks, &diff.asks);
+        self.last_update_id = diff.final_update_id;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sequence {
+    /// Already contained in the book.
+    Stale,
+    Next,
+    Gap,
+}
+
+/// Keeps a `LocalOrderBook` in sync with a diff depth stream (`<symbol>@depth`), following the
+/// procedure Binance documents for spot and futures:
+///
+/// 1. Diffs are buffered while the REST depth snapshot is fetched.
+/// 2. Diffs already contained in the snapshot are dropped, and the first one applied must straddle it.
+/// 3. Each later diff must continue the previous one: its `U` is the previous `u + 1` on spot,
+///    its `pu` is the previous `u` on futures.
+///
+/// When a gap is detected, the book is dropped and rebuilt from a new snapshot. A snapshot older
+/// than the buffered diffs is fetched again only after a growing delay, buffering the diffs
+/// meanwhile. Call `reset` after the websocket reconnected.
+pub struct OrderBookManager {
+    binance: Binance,
+    product: Product,
+    symbol: String,
+    limit: u64,
+    book: LocalOrderBook,
+    synced: bool,
+    /// The first diff after the snapshot has not been applied yet.
+    awaiting_first: bool,
+    buffer: Vec<DepthDiff>,
+    /// Consecutive snapshots that were older than the buffered diffs, and when the last was taken.
+    outdated_snapshots: u32,
+    last_snapshot_at: Option<Instant>,
+}
+
+impl OrderBookManager {
+    /// Only `Product::Spot` and `Product::UsdMFutures` are supported.
+    pub fn new(binance: Binance, product: Product, symbol: &str) -> Self {
+        Self {
+            binance,
+            product,
+            symbol: symbol.to_uppercase(),
+            limit: 1000,
+            book: LocalOrderBook::default(),
+            synced: false,
+            awaiting_first: false,
+            buffer: vec![],
+            outdated_snapshots: 0,
+            last_snapshot_at: None,
+        }
+    }
+
+    /// The depth of the REST snapshot, 1000 by default.
+    pub fn set_snapshot_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// The book, only meaningful while `is_synced`.
+    pub fn book(&self) -> &LocalOrderBook {
+        &self.book
+    }
+
+    /// Drop the book, it is rebuilt from a new snapshot on the next diff.
+    pub fn reset(&mut self) {
+        self.synced = false;
+        self.awaiting_first = false;
+        self.buffer.clear();
+        self.book = LocalOrderBook::default();
+    }
+
+    /// Apply a diff, fetching a snapshot first when the book is not in sync.
+    /// Diffs of other symbols are ignored.
+    #[throws(BinanceError)]
+    pub async fn update(&mut self, diff: DepthDiff) {
+        if self.push(diff, Instant::now()) {
+            let snapshot = self.snapshot().await?;
+            self.resync(snapshot, Instant::now());
+        }
+    }
+
+    /// Apply or buffer `diff`. Returns whether a snapshot has to be fetched.
+    fn push(&mut self, diff: DepthDiff, now: Instant) -> bool {
+        if !diff.symbol.eq_ignore_ascii_case(&self.symbol) {
+            return false;
+        }
+
+        if self.synced {
+            match self.sequence(&diff) {
+                Sequence::Stale => return false,
+                Sequence::Next => {
+                    self.awaiting_first = false;
+                    self.book.apply(&diff);
+                    return false;
+                }
+                Sequence::Gap => {
+                    warn!(
+                        "[OrderBook] gap in {} after update {}, resyncing",
+                        self.symbol,
+                        self.book.last_update_id()
+                    );
+                    self.reset();
+                }
+            }
+        }
+
+        self.buffer.push(diff);
+        match (self.outdated_snapshots, self.last_snapshot_at) {
+            (0, _) | (_, None) => true,
+            (n, Some(at)) => {
+                let delay = SNAPSHOT_RETRY_DELAY
+                    .saturating_mul(1 << (n - 1).min(16))
+                    .min(MAX_SNAPSHOT_RETRY_DELAY);
+                now.duration_since(at) >= delay
+            }
+        }
+    }
+
+    /// Replace the book with `snapshot` and apply the buffered diffs on top of it.
+    fn resync(&mut self, snapshot: LocalOrderBook, now: Instant) {
+        self.book = snapshot;
+        self.synced = true;
+        self.awaiting_first = true;
+        self.last_snapshot_at = Some(now);
+
+        let mut buffered = std::mem::take(&mut self.buffer).into_iter();
+        while let Some(diff) = buffered.next() {
+            match self.sequence(&diff) {
+                Sequence::Stale => {}
+                Sequence::Next => {
+                    self.awaiting_first = false;
+                    self.book.apply(&diff);
+                }
+                Sequence::Gap => {
+                    // The snapshot is older than the buffered diffs, or they miss an update.
+                    debug!("[OrderBook] snapshot of {} is outdated", self.symbol);
+                    self.synced = false;
+                    self.awaiting_first = false;
+                    self.book = LocalOrderBook::default();
+                    self.buffer.push(diff);
+                    self.buffer.extend(buffered);
+                    self.outdated_snapshots += 1;
+                    return;
+                }
+            }
+        }
+        self.outdated_snapshots = 0;
+    }
+
+    fn sequence(&self, diff: &DepthDiff) -> Sequence {
+        sequence(self.book.last_update_id(), self.awaiting_first, diff)
+    }
+
+    #[throws(BinanceError)]
+    async fn snapshot(&self) -> LocalOrderBook {
+        let (last_update_id, bids, asks) = match self.product {
+            Product::Spot => {
+                let book = self
+                    .binance
+                    .request(spot::OrderBookRequest {
+                        symbol: self.symbol.clone(),
+                        limit: Some(self.limit),
+                    })
+                    .await?;
+                let bids = book.bids.iter().map(|l| (l.price, l.qty)).collect();
+                let asks = book.asks.iter().map(|l| (l.price, l.qty)).collect();
+                (book.last_update_id, bids, asks)
+            }
+            Product::UsdMFutures => {
+                let book = self
+                    .binance
+                    .request(usdm::OrderBookRequest {
+                        symbol: self.symbol.clone(),
+                        limit: Some(self.limit),
+                    })
+                    .await?;
+                let bids = book.bids.iter().map(|l| (l.price, l.qty)).collect();
+                let asks = book.asks.iter().map(|l| (l.price, l.qty)).collect();
+                (book.last_update_id, bids, asks)
+            }
+            product => throw!(InvalidConfig(format!(
+                "order book snapshots are not supported for {product:?}"
+            ))),
+        };
+
+        LocalOrderBook {
+            last_update_id,
+            bids,
+            asks,
+        }
+    }
+}
+
+/// Where `diff` falls relative to a book at `last_update_id`.
+fn sequence(last_update_id: u64, awaiting_first: bool, diff: &DepthDiff) -> Sequence {
+    let (first, last) = (diff.first_update_id, diff.final_update_id);
+    match (diff.prev_final_update_id, awaiting_first) {
+        // Spot: U <= lastUpdateId + 1 <= u for the first event, then U == previous u + 1.
+        (None, _) if last <= last_update_id => Sequence::Stale,
+        (None, true) if first <= last_update_id + 1 => Sequence::Next,
+        (None, false) if first == last_update_id + 1 => Sequence::Next,
+        // Futures: U <= lastUpdateId <= u for the first event, then pu == previous u.
+        (Some(_), _) if last < last_update_id => Sequence::Stale,
+        (Some(_), true) if first <= last_update_id => Sequence::Next,
+        (Some(prev), false) if prev == last_update_id => Sequence::Next,
+        _ => Sequence::Gap,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sequence, DepthDiff, LocalOrderBook, OrderBookManager, Sequence};
+    use crate::{models::Product, rest::Binance};
+    use rust_decimal::Decimal;
+    use std::time::{Duration, Instant};
+
+    fn diff(first: u64, last: u64, prev: Option<u64>) -> DepthDiff {
+        DepthDiff {
+            symbol: "BTCUSDT".into(),
+            first_update_id: first,
+            final_update_id: last,
+            prev_final_update_id: prev,
+            bids: vec![],
+            asks: vec![],
+        }
+    }
+
+    #[test]
+    fn spot_sequence() {
+        assert_eq!(sequence(100, true, &diff(90, 100, None)), Sequence::Stale);
+        assert_eq!(sequence(100, true, &diff(95, 105, None)), Sequence::Next);
+        assert_eq!(sequence(100, true, &diff(102, 105, None)), Sequence::Gap);
+        assert_eq!(sequence(105, false, &diff(106, 110, None)), Sequence::Next);
+        assert_eq!(sequence(105, false, &diff(107, 110, None)), Sequence::Gap);
+    }
+
+    #[test]
+    fn futures_sequence() {
+        assert_eq!(
+            sequence(100, true, &diff(80, 99, Some(79))),
+            Sequence::Stale
+        );
+        assert_eq!(
+            sequence(100, true, &diff(95, 100, Some(94))),
+            Sequence::Next
+        );
+        assert_eq!(
+            sequence(100, true, &diff(101, 105, Some(100))),
+            Sequence::Gap
+        );
+        assert_eq!(
+            sequence(105, false, &diff(108, 110, Some(105))),
+            Sequence::Next
+        );
+        assert_eq!(
+            sequence(105, false, &diff(106, 110, Some(104))),
+            Sequence::Gap
+        );
+    }
+
+    #[test]
+    fn apply_levels() {
+        let mut book = LocalOrderBook::default();
+        let mut d = diff(1, 1, None);
+        d.bids = vec![
+            (Decimal::new(100, 0), Decimal::ONE),
+            (Decimal::new(99, 0), Decimal::TWO),
+        ];
+        d.asks = vec![(Decimal::new(101, 0), Decimal::ONE)];
+        book.apply(&d);
+        assert_eq!(book.best_bid(), Some((Decimal::new(100, 0), Decimal::ONE)));
+        assert_eq!(book.best_ask(), Some((Decimal::new(101, 0), Decimal::ONE)));
+
+        d.bids = vec![(Decimal::new(100, 0), Decimal::ZERO)];
+        d.asks = vec![];
+        book.apply(&d);
+        assert_eq!(book.best_bid(), Some((Decimal::new(99, 0), Decimal::TWO)));
+        assert_eq!(book.bids().count(), 1);
+    }
+
+    #[test]
+    fn outdated_snapshot_backoff() {
+        let mut manager = OrderBookManager::new(Binance::new(), Product::Spot, "btcusdt");
+        let snapshot = |last_update_id| LocalOrderBook {
+            last_update_id,
+            ..Default::default()
+        };
+        let start = Instant::now();
+
+        assert!(manager.push(diff(101, 110, None), start));
+        // Fetched while the stream was already ahead of it.
+        manager.resync(snapshot(90), start);
+        assert!(!manager.is_synced());
+
+        // More diffs are buffered instead of fetching a snapshot for each of them.
+        for i in 0..10 {
+            let first = 111 + i * 10;
+            assert!(!manager.push(diff(first, first + 9, None), start));
+        }
+        assert!(manager.push(diff(211, 220, None), start + Duration::from_secs(1)));
+
+        manager.resync(snapshot(150), start + Duration::from_secs(1));
+        assert!(manager.is_synced());
+        assert_eq!(manager.book().last_update_id(), 220);
+        assert!(!manager.push(diff(221, 230, None), start + Duration::from_secs(1)));
+        assert_eq!(manager.book().last_update_id(), 230);
+    }
+}
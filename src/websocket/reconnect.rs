@@ -0,0 +1,307 @@
+use super::{topic_params, BinanceWebsocket, Heartbeat, MethodResponse, ParseMessage};
+use crate::{error::BinanceError, Config};
+use fehler::{throw, throws};
+use futures::{future::BoxFuture, stream::Stream, FutureExt, StreamExt};
+use log::{debug, warn};
+use serde_json::Value;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::{sleep, Sleep};
+
+/// An item of a `ReconnectingWebsocket`.
+#[derive(Debug)]
+pub enum WebsocketEvent<M> {
+    Message(M),
+    /// The connection was lost, `None` when the server closed it. Messages may have been
+    /// missed from here until the next `Reconnected`.
+    Disconnected(Option<BinanceError>),
+    /// A new connection is established and subscribed to the same topics.
+    Reconnected,
+}
+
+/// How long to wait between reconnection attempts.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Give up after this many consecutive failed attempts, `None` to retry forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub(crate) fn delay(&self, failures: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(1 << failures.min(16))
+            .min(self.max_delay)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionHealth {
+    pub connected: bool,
+    pub connected_since: Option<Instant>,
+    pub last_message_at: Option<Instant>,
+    /// Messages received over all connections.
+    pub messages: u64,
+    pub reconnects: u64,
+    /// Failed connection attempts since the last successful one.
+    pub failures: u32,
+}
+
+enum State<M> {
+    Connected(Box<BinanceWebsocket<M>>),
+    Waiting(Pin<Box<Sleep>>),
+    Connecting(BoxFuture<'static, Result<BinanceWebsocket<M>, BinanceError>>),
+    Terminated,
+}
+
+/// A `BinanceWebsocket` that reconnects with backoff when the connection drops, including
+/// the forced disconnect Binance does every 24 hours, and subscribes to the same topics again.
+pub struct ReconnectingWebsocket<M> {
+    config: Config,
+    topics: Vec<String>,
+    policy: ReconnectPolicy,
+    heartbeat: Heartbeat,
+    health: ConnectionHealth,
+    state: State<M>,
+}
+
+impl<M> ReconnectingWebsocket<M>
+where
+    M: ParseMessage + Send + 'static,
+{
+    #[throws(BinanceError)]
+    pub async fn new<I, S>(topics: I) -> ReconnectingWebsocket<M>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::with_config(&Config::default(), topics).await?
+    }
+
+    /// Connect for the first time. Unlike later reconnections, a failure here is returned.
+    #[throws(BinanceError)]
+    pub async fn with_config<I, S>(config: &Config, topics: I) -> ReconnectingWebsocket<M>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let topics: Vec<String> = topics.into_iter().map(|t| t.as_ref().into()).collect();
+        let ws = BinanceWebsocket::with_config(config, &topics).await?;
+
+        Self {
+            config: config.clone(),
+            topics,
+            policy: ReconnectPolicy::default(),
+            heartbeat: Heartbeat::default(),
+            health: ConnectionHealth {
+                connected: true,
+                connected_since: Some(Instant::now()),
+                ..Default::default()
+            },
+            state: State::Connected(Box::new(ws)),
+        }
+    }
+
+    /// Subscribe to `topics` on the current connection and on every reconnection.
+    #[throws(BinanceError)]
+    pub async fn subscribe<I, S>(&mut self, topics: I) -> MethodResponse<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let topics: Vec<String> = topics.into_iter().map(|t| t.as_ref().into()).collect();
+        for topic in &topics {
+            if !self.topics.contains(topic) {
+                self.topics.push(topic.clone());
+            }
+        }
+        self.forward("SUBSCRIBE", topics).await?
+    }
+
+    /// Unsubscribe from `topics`. A reconnection needs at least one topic, so removing the
+    /// last one fails with `EmptyTopics` and leaves the subscriptions unchanged.
+    #[throws(BinanceError)]
+    pub async fn unsubscribe<I, S>(&mut self, topics: I) -> MethodResponse<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let topics: Vec<String> = topics.into_iter().map(|t| t.as_ref().into()).collect();
+        if self.topics.iter().all(|t| topics.contains(t)) {
+            throw!(BinanceError::EmptyTopics);
+        }
+        self.topics.retain(|t| !topics.contains(t));
+        self.forward("UNSUBSCRIBE", topics).await?
+    }
+
+    /// The subscriptions of the current connection as reported by Binance.
+    #[throws(BinanceError)]
+    pub async fn list_subscriptions(&mut self) -> MethodResponse<Vec<String>> {
+        match &mut self.state {
+            State::Connected(ws) => ws.list_subscriptions().await?,
+            _ => throw!(BinanceError::WebsocketClosed),
+        }
+    }
+
+    /// Send a topic change to the current connection. Without one, the change takes effect
+    /// on the next connection and the response resolves immediately.
+    #[throws(BinanceError)]
+    async fn forward(&mut self, method: &str, topics: Vec<String>) -> MethodResponse<()> {
+        if let State::Connected(ws) = &mut self.state {
+            ws.call(method, topic_params(topics)).await?
+        } else {
+            if let State::Connecting(_) = self.state {
+                // The pending connection uses the old topics.
+                self.state = self.connect();
+            }
+            MethodResponse::ready(Value::Null)
+        }
+    }
+
+    fn connect(&self) -> State<M> {
+        let config = self.config.clone();
+        let topics = self.topics.clone();
+        let heartbeat = self.heartbeat.clone();
+        State::Connecting(
+            async move {
+                let mut ws = BinanceWebsocket::with_config(&config, &topics).await?;
+                ws.set_heartbeat(heartbeat);
+                Ok(ws)
+            }
+            .boxed(),
+        )
+    }
+}
+
+impl<M> ReconnectingWebsocket<M> {
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.policy = policy;
+    }
+
+    /// Applies to the current and all later connections. An `idle_timeout` makes a silent
+    /// connection reconnect.
+    pub fn set_heartbeat(&mut self, heartbeat: Heartbeat) {
+        if let State::Connected(ws) = &mut self.state {
+            ws.set_heartbeat(heartbeat.clone());
+        }
+        self.heartbeat = heartbeat;
+    }
+
+    pub fn topics(&self) -> &[String] {
+        &self.topics
+    }
+
+    pub fn health(&self) -> &ConnectionHealth {
+        &self.health
+    }
+
+    fn wait(&self) -> State<M> {
+        let delay = self.policy.delay(self.health.failures);
+        debug!("[WS] reconnecting in {delay:?}");
+        State::Waiting(Box::pin(sleep(delay)))
+    }
+}
+
+/// Errors after which the connection is unusable, as opposed to a single unparsable message.
+pub(crate) fn is_disconnect(e: &BinanceError) -> bool {
+    matches!(
+        e,
+        BinanceError::Websocket(_)
+            | BinanceError::WebsocketClosed
+            | BinanceError::WebsocketTimeout(_)
+    )
+}
+
+impl<M> Stream for ReconnectingWebsocket<M>
+where
+    M: ParseMessage + Send + Unpin + fmt::Debug + 'static,
+{
+    type Item = Result<WebsocketEvent<M>, BinanceError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match &mut this.state {
+                State::Connected(ws) => {
+                    let reason = match ws.poll_next_unpin(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Some(Ok(m))) => {
+                            this.health.messages += 1;
+                            this.health.last_message_at = Some(Instant::now());
+                            return Poll::Ready(Some(Ok(WebsocketEvent::Message(m))));
+                        }
+                        Poll::Ready(Some(Err(e))) if !is_disconnect(&e) => {
+                            return Poll::Ready(Some(Err(e)))
+                        }
+                        Poll::Ready(Some(Err(e))) => Some(e),
+                        Poll::Ready(None) => None,
+                    };
+
+                    warn!("[WS] disconnected: {reason:?}");
+                    this.health.connected = false;
+                    this.health.connected_since = None;
+                    this.state = this.wait();
+                    return Poll::Ready(Some(Ok(WebsocketEvent::Disconnected(reason))));
+                }
+                State::Waiting(delay) => {
+                    if delay.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.state = this.connect();
+                }
+                State::Connecting(fut) => match fut.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(ws)) => {
+                        this.health.connected = true;
+                        this.health.connected_since = Some(Instant::now());
+                        this.health.reconnects += 1;
+                        this.health.failures = 0;
+                        this.state = State::Connected(Box::new(ws));
+                        return Poll::Ready(Some(Ok(WebsocketEvent::Reconnected)));
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.health.failures += 1;
+                        if matches!(this.policy.max_attempts, Some(max) if this.health.failures >= max)
+                        {
+                            this.state = State::Terminated;
+                        } else {
+                            this.state = this.wait();
+                        }
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                State::Terminated => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReconnectPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn reconnect_delay() {
+        let policy = ReconnectPolicy::default();
+        assert_eq!(policy.delay(0), Duration::from_millis(500));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(20), Duration::from_secs(30));
+    }
+}
@@ -1,5 +1,11 @@
 use crate::{
-    error::BinanceError, models::Product, parser::string_or_decimal, websocket::ParseMessage,
+    error::BinanceError,
+    models::{spot::UserDataStream, Product},
+    parser::string_or_decimal,
+    rest::spot::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+    },
+    websocket::{ParseMessage, UserDataMessage},
 };
 use fehler::throws;
 use rust_decimal::Decimal;
@@ -50,6 +56,32 @@ impl ParseMessage for WebsocketMessage {
     }
 }
 
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &UserDataStream) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(listen_key: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {
+            listen_key: listen_key.into(),
+        }
+    }
+
+    fn close(listen_key: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {
+            listen_key: listen_key.into(),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::ListenKeyExpired(_))
+    }
+}
+
 /// The Aggregate Trade Streams push trade information that is aggregated for a single taker order.
 ///
 /// <https://github.com/binance/binance-spot-api-docs/blob/master/web-socket-streams.md#aggregate-trade-streams>
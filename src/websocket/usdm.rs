@@ -1,11 +1,14 @@
 use crate::{
     error::BinanceError::{
-        self, EmptyUserDataStream, StreamNotImplemented, UnknownStream,
-        UserDataStreamEventNotImplemented,
+        self, EmptyUserDataStream, UnknownStream, UserDataStreamEventNotImplemented,
     },
     models::{ExecutionType, OrderStatus, OrderType, Product, Side, TimeInForce},
     parser::{string_or_decimal, string_or_decimal_opt},
-    websocket::ParseMessage,
+    rest::usdm::{
+        CloseUserDataStreamRequest, KeepaliveUserDataStreamRequest, StartUserDataStreamRequest,
+        StartUserDataStreamResponse,
+    },
+    websocket::{ParseMessage, UserDataMessage},
 };
 use fehler::{throw, throws};
 use rust_decimal::Decimal;
@@ -22,15 +25,23 @@ pub enum WebsocketMessage {
     UserDataStreamExpired,
     // Market Stream
     AggregateTrade(super::models::AggregateTrade),
+    MarkPrice(MarkPrice),
+    MarkPriceAll(Vec<MarkPrice>),
+    Kline(Kline),
+    ContinuousKline(ContinuousKline),
+    MiniTicker(MiniTicker),
+    MiniTickerAll(Vec<MiniTicker>),
+    Ticker(Ticker),
+    TickerAll(Vec<Ticker>),
     BookTicker(BookTicker),
-    // Trade(TradeMessage),
-    // Candlestick(CandelStickMessage),
-    // MiniTicker(MiniTicker),
-    // MiniTickerAll(Vec<MiniTicker>),
-    // Ticker(Ticker),
-    // TickerAll(Vec<Ticker>),
-    // OrderBook(OrderBook),
-    // Depth(Depth),
+    /// Both `<symbol>@forceOrder` and `!forceOrder@arr` push one liquidation per message.
+    ForceOrder(ForceOrder),
+    /// `<symbol>@depth<levels>`
+    PartialDepth(DepthUpdate),
+    /// `<symbol>@depth`, the diffs to maintain a local order book from.
+    DepthUpdate(DepthUpdate),
+    CompositeIndex(CompositeIndex),
+    ContractInfo(ContractInfo),
 }
 
 impl ParseMessage for WebsocketMessage {
@@ -40,36 +51,36 @@ impl ParseMessage for WebsocketMessage {
     fn parse(stream: &str, data: &str) -> Self {
         if stream.ends_with("@aggTrade") {
             Self::AggregateTrade(from_str(data)?)
-        } else if stream.contains("@markPrice") {
-            throw!(StreamNotImplemented(stream.into()))
         } else if stream.starts_with("!markPrice@arr") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::MarkPriceAll(from_str(data)?)
+        } else if stream.contains("@markPrice") {
+            Self::MarkPrice(from_str(data)?)
         } else if stream.contains("@kline_") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::Kline(from_str(data)?)
         } else if stream.contains("@continuousKline_") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@miniTicker") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::ContinuousKline(from_str(data)?)
         } else if stream == "!miniTicker@arr" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@ticker") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::MiniTickerAll(from_str(data)?)
+        } else if stream.ends_with("@miniTicker") {
+            Self::MiniTicker(from_str(data)?)
         } else if stream == "!ticker@arr" {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::TickerAll(from_str(data)?)
+        } else if stream.ends_with("@ticker") {
+            Self::Ticker(from_str(data)?)
         } else if stream.ends_with("@bookTicker") || stream == "!bookTicker" {
             Self::BookTicker(from_str(data)?)
-        } else if stream.ends_with("@forceOrder") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream == "!forceOrder@arr" {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.ends_with("@depth") || stream.contains("@depth@") {
-            throw!(StreamNotImplemented(stream.into()))
-        } else if stream.contains("@depth") {
-            throw!(StreamNotImplemented(stream.into()))
+        } else if stream.ends_with("@forceOrder") || stream == "!forceOrder@arr" {
+            Self::ForceOrder(from_str(data)?)
+        } else if let Some((_, levels)) = stream.split_once("@depth") {
+            if levels.starts_with(|c: char| c.is_ascii_digit()) {
+                Self::PartialDepth(from_str(data)?)
+            } else {
+                Self::DepthUpdate(from_str(data)?)
+            }
         } else if stream.ends_with("@compositeIndex") {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::CompositeIndex(from_str(data)?)
         } else if stream == "!contractInfo" {
-            throw!(StreamNotImplemented(stream.into()))
+            Self::ContractInfo(from_str(data)?)
         } else if stream.len() == 64 {
             // is a user stream
             let value: UserDataStreamEvent = from_str(data)?;
@@ -93,6 +104,28 @@ impl ParseMessage for WebsocketMessage {
     }
 }
 
+impl UserDataMessage for WebsocketMessage {
+    type Start = StartUserDataStreamRequest;
+    type Keepalive = KeepaliveUserDataStreamRequest;
+    type Close = CloseUserDataStreamRequest;
+
+    fn listen_key(resp: &StartUserDataStreamResponse) -> &str {
+        &resp.listen_key
+    }
+
+    fn keepalive(_: &str) -> KeepaliveUserDataStreamRequest {
+        KeepaliveUserDataStreamRequest {}
+    }
+
+    fn close(_: &str) -> CloseUserDataStreamRequest {
+        CloseUserDataStreamRequest {}
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self, Self::UserDataStreamExpired)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserDataStreamEvent {
@@ -283,3 +316,388 @@ pub struct BookTicker {
     #[serde(rename = "A", with = "string_or_decimal")]
     pub best_ask_qty: Decimal,
 }
+
+/// `<symbol>@markPrice` or `<symbol>@markPrice@1s`, and as an array for all symbols.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPrice {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub mark_price: Decimal,
+    #[serde(rename = "i", with = "string_or_decimal")]
+    pub index_price: Decimal,
+    /// Only meaningful in the last hour before settlement.
+    #[serde(rename = "P", with = "string_or_decimal")]
+    pub estimated_settle_price: Decimal,
+    #[serde(rename = "r", with = "string_or_decimal")]
+    pub funding_rate: Decimal,
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Kline {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+/// `<pair>_<contractType>@continuousKline_<interval>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContinuousKline {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: String,
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KlineData {
+    #[serde(rename = "t")]
+    pub start_time: u64,
+    #[serde(rename = "T")]
+    pub close_time: u64,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "o", with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub close_price: Decimal,
+    #[serde(rename = "h", with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub base_asset_volume: Decimal,
+    #[serde(rename = "n")]
+    pub number_of_trades: u64,
+    #[serde(rename = "x")]
+    pub is_closed: bool,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub quote_asset_volume: Decimal,
+    #[serde(rename = "V", with = "string_or_decimal")]
+    pub taker_buy_base_asset_volume: Decimal,
+    #[serde(rename = "Q", with = "string_or_decimal")]
+    pub taker_buy_quote_asset_volume: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MiniTicker {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub close_price: Decimal,
+    #[serde(rename = "o", with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(rename = "h", with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub base_asset_volume: Decimal,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub quote_asset_volume: Decimal,
+}
+
+/// The rolling 24hr statistics of a symbol.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ticker {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price_change: Decimal,
+    #[serde(rename = "P", with = "string_or_decimal")]
+    pub price_change_percent: Decimal,
+    #[serde(rename = "w", with = "string_or_decimal")]
+    pub weighted_average_price: Decimal,
+    #[serde(rename = "c", with = "string_or_decimal")]
+    pub last_price: Decimal,
+    #[serde(rename = "Q", with = "string_or_decimal")]
+    pub last_qty: Decimal,
+    #[serde(rename = "o", with = "string_or_decimal")]
+    pub open_price: Decimal,
+    #[serde(rename = "h", with = "string_or_decimal")]
+    pub high_price: Decimal,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub low_price: Decimal,
+    #[serde(rename = "v", with = "string_or_decimal")]
+    pub base_asset_volume: Decimal,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub quote_asset_volume: Decimal,
+    #[serde(rename = "O")]
+    pub open_time: u64,
+    #[serde(rename = "C")]
+    pub close_time: u64,
+    #[serde(rename = "F")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "n")]
+    pub number_of_trades: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForceOrder {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "o")]
+    pub order: LiquidationOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiquidationOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_decimal")]
+    pub original_qty: Decimal,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "ap", with = "string_or_decimal")]
+    pub average_price: Decimal,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "l", with = "string_or_decimal")]
+    pub last_filled_qty: Decimal,
+    #[serde(rename = "z", with = "string_or_decimal")]
+    pub accumulated_filled_qty: Decimal,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DepthUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub final_update_id: u64,
+    /// The final update id of the previous event.
+    #[serde(rename = "pu")]
+    pub prev_final_update_id: u64,
+    /// Price and quantity of the changed levels, a quantity of zero removes the level.
+    #[serde(rename = "b")]
+    pub bids: Vec<(Decimal, Decimal)>,
+    #[serde(rename = "a")]
+    pub asks: Vec<(Decimal, Decimal)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompositeIndex {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_decimal")]
+    pub price: Decimal,
+    #[serde(rename = "C")]
+    pub component_type: String,
+    #[serde(rename = "c")]
+    pub composition: Vec<IndexComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexComponent {
+    #[serde(rename = "b")]
+    pub base_asset: String,
+    #[serde(rename = "q")]
+    pub quote_asset: String,
+    #[serde(rename = "w", with = "string_or_decimal")]
+    pub weight_in_quantity: Decimal,
+    #[serde(rename = "W", with = "string_or_decimal")]
+    pub weight_in_percentage: Decimal,
+    #[serde(rename = "i", with = "string_or_decimal")]
+    pub index_price: Decimal,
+}
+
+/// Pushed when a contract is listed, settled or its leverage brackets change.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContractInfo {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: String,
+    #[serde(rename = "dt")]
+    pub delivery_date: u64,
+    #[serde(rename = "ot")]
+    pub onboard_date: u64,
+    #[serde(rename = "cs")]
+    pub contract_status: String,
+    /// Only present when the brackets changed.
+    #[serde(rename = "bks", default)]
+    pub brackets: Vec<ContractBracket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContractBracket {
+    #[serde(rename = "bs")]
+    pub bracket: u64,
+    #[serde(rename = "bnf", with = "string_or_decimal")]
+    pub notional_floor: Decimal,
+    #[serde(rename = "bnc", with = "string_or_decimal")]
+    pub notional_cap: Decimal,
+    #[serde(rename = "mmr", with = "string_or_decimal")]
+    pub maintenance_ratio: Decimal,
+    #[serde(rename = "cf", with = "string_or_decimal")]
+    pub cum: Decimal,
+    #[serde(rename = "mi")]
+    pub min_leverage: u64,
+    #[serde(rename = "ma")]
+    pub max_leverage: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::WebsocketMessage;
+    use crate::{models::Side, websocket::ParseMessage};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn market_streams() {
+        let fixtures: [(&str, &str, fn(&WebsocketMessage) -> bool); 11] = [
+            (
+                "btcusdt@markPrice@1s",
+                r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11794.15000000","i":"11784.62659091","P":"11784.25641265","r":"0.00038167","T":1562306400000}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MarkPrice(m)
+                    if m.mark_price == Decimal::new(1179415, 2) && m.funding_rate == Decimal::new(38167, 8))
+                },
+            ),
+            (
+                "!markPrice@arr",
+                r#"[{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11185.87786614","i":"11784.62659091","P":"11784.25641265","r":"0.00030000","T":1562306400000}]"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MarkPriceAll(m)
+                    if m.len() == 1 && m[0].next_funding_time == 1562306400000)
+                },
+            ),
+            (
+                "bnbusdt@kline_1m",
+                r#"{"e":"kline","E":1638747660000,"s":"BNBUSDT","k":{"t":1638747660000,"T":1638747719999,"s":"BNBUSDT","i":"1m","f":100,"L":200,"o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,"q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Kline(k)
+                    if k.symbol == "BNBUSDT" && k.kline.close_price == Decimal::new(20, 4) && !k.kline.is_closed)
+                },
+            ),
+            (
+                "btcusdt_perpetual@continuousKline_1m",
+                r#"{"e":"continuous_kline","E":1607443058651,"ps":"BTCUSDT","ct":"PERPETUAL","k":{"t":1607443020000,"T":1607443079999,"i":"1m","f":116467658886,"L":116468012423,"o":"18787.00","c":"18804.04","h":"18804.04","l":"18786.54","v":"197.664","n":543,"x":false,"q":"3715253.19494","V":"184.769","Q":"3472925.84746","B":"0"}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::ContinuousKline(k)
+                    if k.contract_type == "PERPETUAL" && k.kline.number_of_trades == 543)
+                },
+            ),
+            (
+                "btcusdt@miniTicker",
+                r#"{"e":"24hrMiniTicker","E":123456789,"s":"BTCUSDT","c":"0.0025","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18"}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MiniTicker(t)
+                    if t.close_price == Decimal::new(25, 4) && t.quote_asset_volume == Decimal::new(18, 0))
+                },
+            ),
+            (
+                "!miniTicker@arr",
+                r#"[{"e":"24hrMiniTicker","E":123456789,"s":"ETHUSDT","c":"1800.50","o":"1750.00","h":"1820.00","l":"1740.00","v":"10000","q":"18000000"}]"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::MiniTickerAll(t)
+                    if t.len() == 1 && t[0].symbol == "ETHUSDT" && t[0].close_price == Decimal::new(180050, 2))
+                },
+            ),
+            (
+                "btcusdt@ticker",
+                r#"{"e":"24hrTicker","E":123456789,"s":"BTCUSDT","p":"0.0015","P":"250.00","w":"0.0018","c":"0.0025","Q":"10","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18","O":0,"C":86400000,"F":0,"L":18150,"n":18151}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::Ticker(t)
+                    if t.price_change_percent == Decimal::new(250, 0) && t.number_of_trades == 18151)
+                },
+            ),
+            (
+                "!ticker@arr",
+                r#"[{"e":"24hrTicker","E":123456789,"s":"BTCUSDT","p":"0.0015","P":"250.00","w":"0.0018","c":"0.0025","Q":"10","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000","q":"18","O":0,"C":86400000,"F":0,"L":18150,"n":18151}]"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::TickerAll(t)
+                    if t.len() == 1 && t[0].last_trade_id == 18150)
+                },
+            ),
+            (
+                "!forceOrder@arr",
+                r#"{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::ForceOrder(f)
+                    if matches!(f.order.side, Side::Sell) && f.order.original_qty == Decimal::new(14, 3))
+                },
+            ),
+            (
+                "btcusdt@compositeIndex",
+                r#"{"e":"compositeIndex","E":1602310596000,"s":"DEFIUSDT","p":"554.41604065","C":"baseAsset","c":[{"b":"BAL","q":"USDT","w":"1.04884844","W":"0.01457800","i":"24.33521021"}]}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::CompositeIndex(c)
+                    if c.composition.len() == 1 && c.composition[0].base_asset == "BAL")
+                },
+            ),
+            (
+                "!contractInfo",
+                r#"{"e":"contractInfo","E":1669356423908,"s":"IOTAUSDT","ps":"IOTAUSDT","ct":"PERPETUAL","dt":4133404800000,"ot":1569398400000,"cs":"TRADING","bks":[{"bs":1,"bnf":0,"bnc":5000,"mmr":0.01,"cf":0,"mi":21,"ma":50}]}"#,
+                |msg| {
+                    matches!(msg, WebsocketMessage::ContractInfo(c)
+                    if c.contract_status == "TRADING" && c.brackets[0].max_leverage == 50)
+                },
+            ),
+        ];
+
+        for (stream, data, check) in fixtures {
+            match WebsocketMessage::parse(stream, data) {
+                Ok(msg) => assert!(check(&msg), "{}: unexpected {:?}", stream, msg),
+                Err(e) => panic!("{}: {}", stream, e),
+            }
+        }
+    }
+
+    #[test]
+    fn depth_streams() {
+        let data = r#"{"e":"depthUpdate","E":123456789,"T":123456788,"s":"BTCUSDT","U":157,"u":160,"pu":149,"b":[["0.0024","10"]],"a":[["0.0026","100"]]}"#;
+
+        match WebsocketMessage::parse("btcusdt@depth@100ms", data).unwrap() {
+            WebsocketMessage::DepthUpdate(depth) => {
+                assert_eq!(depth.prev_final_update_id, 149);
+                assert_eq!(depth.bids, vec![(Decimal::new(24, 4), Decimal::new(10, 0))]);
+            }
+            msg => panic!("unexpected {:?}", msg),
+        }
+        assert!(matches!(
+            WebsocketMessage::parse("btcusdt@depth5@100ms", data).unwrap(),
+            WebsocketMessage::PartialDepth(_)
+        ));
+    }
+}
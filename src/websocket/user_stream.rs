@@ -0,0 +1,237 @@
+use super::{BinanceWebsocket, ParseMessage, ReconnectPolicy, WebsocketEvent};
+use crate::{
+    error::BinanceError,
+    rest::{Binance, Request},
+};
+use futures::{future::BoxFuture, stream::Stream, FutureExt, StreamExt};
+use log::{debug, warn};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    runtime::Handle,
+    task::JoinHandle,
+    time::{interval_at, sleep, Instant, Sleep},
+};
+
+/// Binance closes a listen key that is not kept alive for 60 minutes.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// The websocket messages of a product that has a user data stream, and the requests managing its listen key.
+pub trait UserDataMessage: ParseMessage + 'static {
+    type Start: Request + Default + Send + Sync + 'static;
+    type Keepalive: Request + Send + Sync + 'static;
+    type Close: Request + Send + Sync + 'static;
+
+    fn listen_key(resp: &<Self::Start as Request>::Response) -> &str;
+    fn keepalive(listen_key: &str) -> Self::Keepalive;
+    fn close(listen_key: &str) -> Self::Close;
+    /// Whether this is the `listenKeyExpired` event.
+    fn is_expired(&self) -> bool;
+}
+
+enum State<M> {
+    Connected(Box<BinanceWebsocket<M>>),
+    Waiting(Pin<Box<Sleep>>),
+    Starting(BoxFuture<'static, Result<(String, BinanceWebsocket<M>), BinanceError>>),
+    Terminated,
+}
+
+/// A user data stream that manages its own listen key: it keeps the key alive in a background task,
+/// starts over with a new key and connection when the key expires or the connection drops, and
+/// closes the key on drop.
+///
+/// `Disconnected` and `Reconnected` events mark the gaps in which events may have been missed.
+pub struct UserDataStream<M>
+where
+    M: UserDataMessage,
+{
+    binance: Binance,
+    listen_key: Option<String>,
+    keepalive: Option<JoinHandle<()>>,
+    policy: ReconnectPolicy,
+    failures: u32,
+    state: State<M>,
+}
+
+impl<M> UserDataStream<M>
+where
+    M: UserDataMessage + Send + 'static,
+{
+    /// Obtain a listen key and connect. Unlike later reconnections, a failure here is returned.
+    pub async fn new(binance: Binance) -> Result<Self, BinanceError> {
+        let (listen_key, ws) = Self::start(binance.clone()).await?;
+        let mut stream = Self {
+            binance,
+            listen_key: None,
+            keepalive: None,
+            policy: ReconnectPolicy::default(),
+            failures: 0,
+            state: State::Connected(Box::new(ws)),
+        };
+        stream.set_listen_key(listen_key);
+        Ok(stream)
+    }
+
+    async fn start(binance: Binance) -> Result<(String, BinanceWebsocket<M>), BinanceError> {
+        let resp = binance.request(M::Start::default()).await?;
+        let listen_key = M::listen_key(&resp).to_string();
+        let ws = BinanceWebsocket::with_config(&binance.config, [&listen_key]).await?;
+        Ok((listen_key, ws))
+    }
+
+    /// Close the old listen key before requesting a new one. Spot and margin hand out the
+    /// active key again, so closing it concurrently could invalidate the new connection.
+    fn restart(&mut self) {
+        self.stop_keepalive();
+        let binance = self.binance.clone();
+        let close = self.listen_key.take().map(|key| M::close(&key));
+        self.state = State::Starting(
+            async move {
+                if let Some(close) = close {
+                    if let Err(e) = binance.request(close).await {
+                        warn!("[WS] failed to close listen key: {e}");
+                    }
+                }
+                Self::start(binance).await
+            }
+            .boxed(),
+        );
+    }
+
+    fn set_listen_key(&mut self, listen_key: String) {
+        let binance = self.binance.clone();
+        let key = listen_key.clone();
+        self.keepalive = Some(tokio::spawn(async move {
+            let mut ticker = interval_at(Instant::now() + KEEPALIVE_INTERVAL, KEEPALIVE_INTERVAL);
+            loop {
+                ticker.tick().await;
+                match binance.request(M::keepalive(&key)).await {
+                    Ok(_) => debug!("[WS] listen key kept alive"),
+                    // The next keepalive may still succeed before the key expires.
+                    Err(e) => warn!("[WS] failed to keep listen key alive: {e}"),
+                }
+            }
+        }));
+        self.listen_key = Some(listen_key);
+    }
+}
+
+impl<M> UserDataStream<M>
+where
+    M: UserDataMessage,
+{
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn listen_key(&self) -> Option<&str> {
+        self.listen_key.as_deref()
+    }
+
+    fn stop_keepalive(&mut self) {
+        if let Some(task) = self.keepalive.take() {
+            task.abort();
+        }
+    }
+}
+
+impl<M> Stream for UserDataStream<M>
+where
+    M: UserDataMessage + Send + Unpin + fmt::Debug + 'static,
+{
+    type Item = Result<WebsocketEvent<M>, BinanceError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match &mut this.state {
+                State::Connected(ws) => {
+                    let reason = match ws.poll_next_unpin(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Some(Ok(m))) if m.is_expired() => None,
+                        Poll::Ready(Some(Ok(m))) => {
+                            return Poll::Ready(Some(Ok(WebsocketEvent::Message(m))))
+                        }
+                        Poll::Ready(Some(Err(e))) if !super::reconnect::is_disconnect(&e) => {
+                            return Poll::Ready(Some(Err(e)))
+                        }
+                        Poll::Ready(Some(Err(e))) => Some(e),
+                        Poll::Ready(None) => None,
+                    };
+
+                    warn!("[WS] user data stream disconnected: {reason:?}");
+                    // Like a failed start, so a server that keeps closing is not hammered.
+                    let delay = this.policy.delay(this.failures);
+                    this.state = State::Waiting(Box::pin(sleep(delay)));
+                    return Poll::Ready(Some(Ok(WebsocketEvent::Disconnected(reason))));
+                }
+                State::Waiting(delay) => {
+                    if delay.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.restart();
+                }
+                State::Starting(fut) => match fut.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok((listen_key, ws))) => {
+                        this.failures = 0;
+                        this.state = State::Connected(Box::new(ws));
+                        this.set_listen_key(listen_key);
+                        return Poll::Ready(Some(Ok(WebsocketEvent::Reconnected)));
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.failures += 1;
+                        if matches!(this.policy.max_attempts, Some(max) if this.failures >= max) {
+                            this.state = State::Terminated;
+                        } else {
+                            let delay = this.policy.delay(this.failures);
+                            this.state = State::Waiting(Box::pin(sleep(delay)));
+                        }
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                State::Terminated => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<M> Drop for UserDataStream<M>
+where
+    M: UserDataMessage,
+{
+    fn drop(&mut self) {
+        self.stop_keepalive();
+
+        let handle = match Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+        let binance = self.binance.clone();
+        let listen_key = self.listen_key.take();
+        // A start in flight creates a key nobody would close otherwise.
+        let starting = match std::mem::replace(&mut self.state, State::Terminated) {
+            State::Starting(fut) => Some(fut),
+            _ => None,
+        };
+        if listen_key.is_none() && starting.is_none() {
+            return;
+        }
+        handle.spawn(async move {
+            let listen_key = match starting {
+                Some(fut) => fut.await.ok().map(|(listen_key, _)| listen_key),
+                None => listen_key,
+            };
+            if let Some(listen_key) = listen_key {
+                if let Err(e) = binance.request(M::close(&listen_key)).await {
+                    warn!("[WS] failed to close listen key: {e}");
+                }
+            }
+        });
+    }
+}
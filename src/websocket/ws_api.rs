@@ -0,0 +1,466 @@
+use super::{connect, WSStream};
+use crate::{
+    error::{
+        BinanceError::{self, *},
+        BinanceResponseError,
+    },
+    models::Product,
+    rest::{self, wrap, Binance, Request, RestResponse},
+};
+use fehler::{throw, throws};
+use futures::{
+    channel::{mpsc, oneshot},
+    stream::SplitStream,
+    FutureExt, StreamExt,
+};
+use http::StatusCode;
+use log::debug;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{from_str, value::RawValue, Map, Value};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::timeout;
+use tungstenite::Message;
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<Reply>>>>;
+
+/// A REST request that can also be sent over the websocket API.
+pub trait WsApiRequest: Request {
+    /// The method of the call on the websocket API, e.g. `order.place`.
+    const WS_METHOD: &'static str;
+}
+
+macro_rules! ws_api_methods {
+    ($($req: ty => $method: literal,)*) => {
+        $(
+            impl WsApiRequest for $req {
+                const WS_METHOD: &'static str = $method;
+            }
+        )*
+    };
+}
+
+ws_api_methods! {
+    rest::spot::PingRequest => "ping",
+    rest::spot::ServerTimeRequest => "time",
+    rest::spot::ExchangeInformationRequest => "exchangeInfo",
+    rest::spot::OrderBookRequest => "depth",
+    rest::spot::RecentTradesRequest => "trades.recent",
+    rest::spot::HistoricalTradesRequest => "trades.historical",
+    rest::spot::AggTradesRequest => "trades.aggregate",
+    rest::spot::KlinesRequest => "klines",
+    rest::spot::UiKlinesRequest => "uiKlines",
+    rest::spot::AvgPriceRequest => "avgPrice",
+    rest::spot::Ticker24hrRequest => "ticker.24hr",
+    rest::spot::RollingWindowTickerRequest => "ticker",
+    rest::spot::TradingDayTickerRequest => "ticker.tradingDay",
+    rest::spot::PriceTickerRequest => "ticker.price",
+    rest::spot::BookTickerRequest => "ticker.book",
+    rest::spot::NewOrderRequest => "order.place",
+    rest::spot::TestNewOrderRequest => "order.test",
+    rest::spot::QueryOrderRequest => "order.status",
+    rest::spot::CancelOrderRequest => "order.cancel",
+    rest::spot::CancelReplaceOrderRequest => "order.cancelReplace",
+    rest::spot::OpenOrdersRequest => "openOrders.status",
+    rest::spot::AllOrdersRequest => "allOrders",
+    rest::spot::NewOcoRequest => "orderList.place.oco",
+    rest::spot::NewOtoRequest => "orderList.place.oto",
+    rest::spot::NewOtocoRequest => "orderList.place.otoco",
+    rest::spot::GetAccountRequest => "account.status",
+    rest::spot::MyTradesRequest => "myTrades",
+    rest::spot::PreventedMatchesRequest => "myPreventedMatches",
+    rest::spot::CommissionRatesRequest => "account.commission",
+    rest::spot::StartUserDataStreamRequest => "userDataStream.start",
+    rest::spot::KeepaliveUserDataStreamRequest => "userDataStream.ping",
+    rest::spot::CloseUserDataStreamRequest => "userDataStream.stop",
+
+    rest::usdm::OrderBookRequest => "depth",
+    rest::usdm::PriceTickerRequest => "ticker.price",
+    rest::usdm::BookTickerRequest => "ticker.book",
+    rest::usdm::NewOrderRequest => "order.place",
+    rest::usdm::ModifyOrderRequest => "order.modify",
+    rest::usdm::CancelOrderRequest => "order.cancel",
+    rest::usdm::QueryOrderRequest => "order.status",
+    rest::usdm::PositionRiskRequest => "account.position",
+    rest::usdm::AccountInformationV2Request => "account.status",
+    rest::usdm::StartUserDataStreamRequest => "userDataStream.start",
+    rest::usdm::KeepaliveUserDataStreamRequest => "userDataStream.ping",
+    rest::usdm::CloseUserDataStreamRequest => "userDataStream.stop",
+}
+
+/// A client for the websocket API, which takes the same requests as `Binance::request` and
+/// answers with the same responses, without the cost of a new HTTP request per call.
+///
+/// All calls share one connection and may run concurrently: each frame carries an id and the
+/// reply with the same id resolves the call. Signed requests are signed one by one with the
+/// credentials of the `Binance` it was connected with, unless the session is authenticated
+/// with `session_logon`.
+pub struct BinanceWsApi {
+    binance: Binance,
+    product: Product,
+    timeout: Duration,
+    outbox: mpsc::UnboundedSender<Message>,
+    pending: Pending,
+    next_id: AtomicU64,
+    logged_on: AtomicBool,
+}
+
+impl BinanceWsApi {
+    /// Connect to the websocket API of `product`, reusing the config, credentials, time sync
+    /// and rate limits of `binance`.
+    #[throws(BinanceError)]
+    pub async fn connect(binance: &Binance, product: Product) -> Self {
+        let endpoint = match binance.config.ws_api_endpoint(product) {
+            Some(endpoint) => endpoint,
+            None => throw!(WsApiUnsupported(product)),
+        };
+        debug!("[WS-API] endpoint: {endpoint}");
+        let (sink, source) = connect(endpoint).await?.split();
+
+        let (outbox, rx) = mpsc::unbounded();
+        let pending = Pending::default();
+        tokio::spawn(rx.map(Ok).forward(sink).map(|result| {
+            if let Err(e) = result {
+                debug!("[WS-API] connection closed while sending: {e}");
+            }
+        }));
+        tokio::spawn(dispatch(source, outbox.clone(), pending.clone()));
+
+        Self {
+            binance: binance.clone(),
+            product,
+            timeout: Duration::from_secs(10),
+            outbox,
+            pending,
+            next_id: AtomicU64::new(1),
+            logged_on: AtomicBool::new(false),
+        }
+    }
+
+    /// Fail calls with `WsApiTimeout` when no reply arrives in `timeout`, 10 seconds by default.
+    /// A timed out order may still have been executed.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    #[throws(BinanceError)]
+    pub async fn request<R>(&self, req: R) -> RestResponse<R::Response>
+    where
+        R: WsApiRequest,
+    {
+        if R::PRODUCT != self.product {
+            throw!(WsApiProductMismatch(R::PRODUCT, self.product));
+        }
+
+        let mut params = to_params(&req)?;
+        // Stamp the request only after waiting, or the wait counts against recvWindow.
+        self.binance.throttle(&req).await?;
+        if R::SIGNED {
+            self.binance.sync_time_if_stale(R::PRODUCT).await?;
+        }
+        let logged_on = self.logged_on.load(Ordering::Acquire);
+        let credentials = if (R::SIGNED || R::KEYED) && !logged_on {
+            let credentials = self.binance.credentials().await?;
+            params.insert("apiKey".into(), credentials.api_key().into());
+            Some(credentials)
+        } else {
+            None
+        };
+        if R::SIGNED {
+            params.insert(
+                "timestamp".into(),
+                self.binance.timestamp(R::PRODUCT).into(),
+            );
+            params.insert("recvWindow".into(), self.binance.config.recv_window.into());
+            if let Some(credentials) = &credentials {
+                let signature = credentials.signature(&signing_payload(&params), "")?;
+                params.insert("signature".into(), signature.into());
+            }
+        }
+
+        wrap(self.call(R::WS_METHOD, params).await?)
+    }
+
+    /// Authenticate the connection with the credentials of `binance`. Signed requests are then
+    /// sent without a signature.
+    ///
+    /// Binance only accepts `session.logon` from API keys signed with an `Ed25519Signer`.
+    #[throws(BinanceError)]
+    pub async fn session_logon(&self) -> SessionStatus {
+        self.binance.sync_time_if_stale(self.product).await?;
+
+        let credentials = self.binance.credentials().await?;
+        let mut params = Map::new();
+        params.insert("apiKey".into(), credentials.api_key().into());
+        params.insert(
+            "timestamp".into(),
+            self.binance.timestamp(self.product).into(),
+        );
+        params.insert("recvWindow".into(), self.binance.config.recv_window.into());
+        let signature = credentials.signature(&signing_payload(&params), "")?;
+        params.insert("signature".into(), signature.into());
+
+        let status = self.call("session.logon", params).await?;
+        self.logged_on.store(true, Ordering::Release);
+        status
+    }
+
+    #[throws(BinanceError)]
+    pub async fn session_status(&self) -> SessionStatus {
+        self.call("session.status", Map::new()).await?
+    }
+
+    /// Drop the authentication of `session_logon`, signed requests are signed one by one again.
+    #[throws(BinanceError)]
+    pub async fn session_logout(&self) -> SessionStatus {
+        let status = self.call("session.logout", Map::new()).await?;
+        self.logged_on.store(false, Ordering::Release);
+        status
+    }
+
+    #[throws(BinanceError)]
+    async fn call<T>(&self, method: &str, params: Map<String, Value>) -> T
+    where
+        T: DeserializeOwned,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let frame = serde_json::to_string(&Call { id, method, params })?;
+        debug!("[WS-API] call: {frame}");
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+        if self.outbox.unbounded_send(Message::Text(frame)).is_err() {
+            self.pending.lock().unwrap().remove(&id);
+            throw!(WebsocketClosed);
+        }
+
+        let reply = match timeout(self.timeout, rx).await {
+            Ok(Ok(reply)) => reply,
+            // The connection dropped before the reply arrived.
+            Ok(Err(_)) => throw!(WebsocketClosed),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                throw!(WsApiTimeout(method.into(), self.timeout));
+            }
+        };
+
+        match (reply.result, reply.error) {
+            (_, Some(e)) => throw!(reply_error(method, reply.status, e)),
+            (Some(result), None) => from_str(result.get())?,
+            (None, None) => from_str("null")?,
+        }
+    }
+}
+
+impl Drop for BinanceWsApi {
+    fn drop(&mut self) {
+        self.outbox.close_channel();
+    }
+}
+
+/// The authentication state of a websocket API connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// The API key the session is authenticated with, `None` before `session.logon`.
+    pub api_key: Option<String>,
+    pub authorized_since: Option<u64>,
+    pub connected_since: u64,
+    pub return_rate_limits: bool,
+    pub server_time: u64,
+}
+
+#[derive(Serialize)]
+struct Call<'a> {
+    id: u64,
+    method: &'a str,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    params: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    id: Option<u64>,
+    status: u16,
+    result: Option<Box<RawValue>>,
+    error: Option<BinanceResponseError>,
+}
+
+/// Route replies to the calls waiting for them until the connection closes.
+async fn dispatch(
+    mut source: SplitStream<WSStream>,
+    outbox: mpsc::UnboundedSender<Message>,
+    pending: Pending,
+) {
+    while let Some(frame) = source.next().await {
+        // tungstenite queues the pong for a ping itself and sends it on the next read.
+        let text = match frame {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(e) => {
+                debug!("[WS-API] connection closed: {e}");
+                break;
+            }
+        };
+
+        let reply: Reply = match from_str(&text) {
+            Ok(reply) => reply,
+            Err(e) => {
+                debug!("[WS-API] unexpected frame {text}: {e}");
+                continue;
+            }
+        };
+        let tx = match reply.id.and_then(|id| pending.lock().unwrap().remove(&id)) {
+            Some(tx) => tx,
+            None => {
+                debug!("[WS-API] reply to unknown request: {text}");
+                continue;
+            }
+        };
+        // The caller may have timed out in the meantime.
+        let _ = tx.send(reply);
+    }
+
+    // Close the outbox before failing the pending calls, so no new call can slip in between.
+    outbox.close_channel();
+    pending.lock().unwrap().clear();
+}
+
+fn reply_error(method: &str, status: u16, e: BinanceResponseError) -> BinanceError {
+    ApiError {
+        status: StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_REQUEST),
+        endpoint: method.into(),
+        code: e.code.into(),
+        msg: e.msg,
+        retry_after: None,
+    }
+}
+
+/// The parameters of `req` as a JSON object, without the ones left unset.
+#[throws(BinanceError)]
+fn to_params<R: Serialize>(req: &R) -> Map<String, Value> {
+    let params = match serde_json::to_value(req)? {
+        Value::Object(params) => params,
+        _ => Map::new(),
+    };
+    params
+        .into_iter()
+        .filter(|(_, v)| !v.is_null())
+        .map(|(k, v)| match v {
+            // Lists are sent as JSON encoded strings over REST, but as arrays over the websocket
+            // API. No other Binance parameter may start with '['.
+            Value::String(s) if s.starts_with('[') => match from_str(&s) {
+                Ok(list) => (k, list),
+                Err(_) => (k, Value::String(s)),
+            },
+            v => (k, v),
+        })
+        .collect()
+}
+
+/// `key=value` pairs sorted by key and joined with '&', the payload signed by the websocket API.
+fn signing_payload(params: &Map<String, Value>) -> String {
+    let mut pairs: Vec<_> = params
+        .iter()
+        .map(|(k, v)| match v {
+            Value::String(s) => format!("{k}={s}"),
+            v => format!("{k}={v}"),
+        })
+        .collect();
+    pairs.sort();
+    pairs.join("&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{signing_payload, to_params, Call, Reply};
+    use crate::{
+        models::{spot::NewOrderResponseType, OrderType, Side, TimeInForce},
+        rest::spot,
+        Binance,
+    };
+    use rust_decimal::Decimal;
+    use serde_json::{from_str, json, to_string};
+
+    #[test]
+    fn signed_params() {
+        let req = spot::NewOrderRequest {
+            symbol: "BTCUSDT".into(),
+            side: Side::Sell,
+            r#type: OrderType::Limit,
+            time_in_force: Some(TimeInForce::GTC),
+            quantity: Some(Decimal::new(1000000, 8)),
+            price: Some(Decimal::new(5200000, 2)),
+            new_order_resp_type: Some(NewOrderResponseType::Ack),
+            ..Default::default()
+        };
+        let mut params = to_params(&req).unwrap();
+        assert!(!params.contains_key("stopPrice"));
+        params.insert(
+            "apiKey".into(),
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A".into(),
+        );
+        params.insert("recvWindow".into(), 100.into());
+        params.insert("timestamp".into(), 1645423376532u64.into());
+
+        let payload = signing_payload(&params);
+        assert_eq!(
+            payload,
+            "apiKey=vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A&newOrderRespType=ACK\
+             &price=52000.00&quantity=0.01000000&recvWindow=100&side=SELL&symbol=BTCUSDT\
+             &timeInForce=GTC&timestamp=1645423376532&type=LIMIT"
+        );
+        let tr = Binance::with_key_and_secret(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A",
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j",
+        );
+        assert_eq!(
+            tr.signature(&payload, "").unwrap(),
+            "cc15477742bd704c29492d96c7ead9414dfd8e0ec4a00f947bb5bb454ddbd08a"
+        );
+    }
+
+    #[test]
+    fn list_params() {
+        let req = spot::PriceTickerRequest {
+            symbols: vec!["BTCUSDT".into(), "ETHUSDT".into()],
+            ..Default::default()
+        };
+        let params = to_params(&req).unwrap();
+        assert_eq!(params["symbols"], json!(["BTCUSDT", "ETHUSDT"]));
+    }
+
+    #[test]
+    fn frames() {
+        let call = Call {
+            id: 7,
+            method: "ping",
+            params: Default::default(),
+        };
+        assert_eq!(to_string(&call).unwrap(), r#"{"id":7,"method":"ping"}"#);
+
+        let reply: Reply = from_str(
+            r#"{"id":7,"status":200,"result":{"serverTime":1656400526260},"rateLimits":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(reply.id, Some(7));
+        assert_eq!(
+            reply.result.unwrap().get(),
+            r#"{"serverTime":1656400526260}"#
+        );
+
+        let reply: Reply = from_str(
+            r#"{"id":null,"status":400,"error":{"code":-1102,"msg":"Mandatory parameter 'symbol' was not sent."}}"#,
+        )
+        .unwrap();
+        assert_eq!(reply.id, None);
+        assert_eq!(reply.error.unwrap().code, -1102);
+    }
+}